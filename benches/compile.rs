@@ -0,0 +1,42 @@
+//! Wertetabelle eines Ausdrucks mit `x` von 0 bis 999: jedes Mal neu mit
+//! [`calculate_with`](calc::calculate_with) berechnet gegenüber einem
+//! einmal übersetzten [`CompiledExpr`](calc::CompiledExpr).
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+#[path = "../src/main.rs"]
+mod calc;
+
+const FORMULA: &str = "3 * x * x - 2 * (4 + 1) * x + wenn x > 500 dann x sonst 7 / 2";
+const POINTS: usize = 1_000;
+
+fn table(c: &mut Criterion) {
+    let settings = calc::Settings::new();
+    let mut group = c.benchmark_group("Wertetabelle");
+    group.throughput(Throughput::Elements(POINTS as u64));
+
+    let inputs: Vec<String> = (0..POINTS).map(|x| FORMULA.replace('x', x.to_string().as_str())).collect();
+    group.bench_function("calculate", |b| {
+        b.iter(|| {
+            inputs.iter().map(|input| calc::calculate_with(input, &settings).unwrap().0).sum::<f64>()
+        })
+    });
+
+    let expr = calc::CompiledExpr::compile(FORMULA, &["x"], &settings).unwrap();
+    group.bench_function("CompiledExpr", |b| {
+        b.iter(|| {
+            (0..POINTS).map(|x| expr.evaluate_number(&[x as f64]).unwrap()).sum::<f64>()
+        })
+    });
+
+    let expr = expr.fold();
+    group.bench_function("CompiledExpr gefaltet", |b| {
+        b.iter(|| {
+            (0..POINTS).map(|x| expr.evaluate_number(&[x as f64]).unwrap()).sum::<f64>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, table);
+criterion_main!(benches);
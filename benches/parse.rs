@@ -0,0 +1,66 @@
+//! Laufzeit vom Zerlegen der Eingabe bis zum fertigen Baum, sie wächst
+//! linear mit der Länge der Eingabe. Die Grenzen der [`Settings`](calc::Settings)
+//! werden dafür aufgehoben.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+#[path = "../src/main.rs"]
+mod calc;
+
+fn settings() -> calc::Settings {
+    let mut settings = calc::Settings::new();
+    settings.limits.input = usize::MAX;
+    settings.limits.tokens = usize::MAX;
+    settings.limits.depth = usize::MAX;
+    settings.limits.steps = usize::MAX;
+    settings
+}
+
+/// `1 + 2 - 3 * 4 + ...` mit `n` Zahlen.
+fn flat_sum(c: &mut Criterion) {
+    let settings = settings();
+    let ops = ["+", "-", "*", "+"];
+    let mut group = c.benchmark_group("flache Summe");
+    for n in [1_000, 2_000, 4_000, 8_000] {
+        let mut input = String::from("1");
+        for i in 1..n {
+            input.push_str(&format!(" {} {}", ops[i % ops.len()], i % 100));
+        }
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &input, |b, input| {
+            b.iter(|| calc::parse(input, &settings).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// `(1 + (1 + (...)))` mit `n` Ebenen.
+fn deep_nesting(c: &mut Criterion) {
+    let settings = settings();
+    let mut group = c.benchmark_group("tiefe Verschachtelung");
+    for n in [100, 200, 400, 800] {
+        let input = format!("{}1{}", "(1 + ".repeat(n), ")".repeat(n));
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &input, |b, input| {
+            b.iter(|| calc::parse(input, &settings).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// `((...(1)...))` mit `n` Klammern um den ganzen Ausdruck.
+fn nested_parentheses(c: &mut Criterion) {
+    let settings = settings();
+    let mut group = c.benchmark_group("Klammern");
+    for n in [1_000, 4_000, 16_000] {
+        let input = format!("{}1{}", "(".repeat(n), ")".repeat(n));
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &input, |b, input| {
+            b.iter(|| calc::parse(input, &settings).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, flat_sum, deep_nesting, nested_parentheses);
+criterion_main!(benches);
@@ -1,4 +1,4 @@
-use crate::calculate;
+use super::{calculate, calculate_with, calculate_value, calculate_steps, calculate_pretty, calculate_export, calculate_tree, calculate_output};
 
 #[test]
 fn rdm_1() {
@@ -65,4 +65,948 @@ fn t7() {
 fn t8() {
     let res = calculate("-((-1 plus 3))").unwrap();
     assert_eq!(-2f64, res.0);
-}
\ No newline at end of file
+}
+fn implicit() -> super::Settings {
+    let mut settings = super::Settings::new();
+    settings.implicit_multiplication = true;
+    settings
+}
+#[test]
+fn implicit_1() {
+    let res = calculate_with("2(3+4)", &implicit()).unwrap();
+    assert_eq!(14f64, res.0);
+    assert_eq!("(2×(3+4))", res.1);
+}
+#[test]
+fn implicit_2() {
+    let res = calculate_with("(1+2)(3+4)", &implicit()).unwrap();
+    assert_eq!(21f64, res.0);
+}
+#[test]
+fn implicit_3() {
+    let res = calculate_with("1 durch 2 auf eins plus eins zu", &implicit()).unwrap();
+    assert_eq!(0.25f64, res.0);
+    assert_eq!("(1÷(2×(1+1)))", res.1);
+}
+#[test]
+fn implicit_4() {
+    let res = calculate_with("3 + 2(1)(4) - 1", &implicit()).unwrap();
+    assert_eq!(10f64, res.0);
+}
+#[test]
+fn implicit_off() {
+    assert!(calculate("2(3+4)").is_err());
+}
+
+#[test]
+fn percent_1() {
+    let res = calculate("19 % von 250").unwrap();
+    assert_eq!(47.5f64, res.0);
+}
+#[test]
+fn percent_2() {
+    let res = calculate("250 plus 19 Prozent").unwrap();
+    assert_eq!(297.5f64, res.0);
+    assert_eq!("(250+19%)", res.1);
+}
+#[test]
+fn percent_3() {
+    let res = calculate("80 - 25%").unwrap();
+    assert_eq!(60f64, res.0);
+}
+#[test]
+fn percent_4() {
+    let res = calculate("5 Promille von 2000").unwrap();
+    assert_eq!(10f64, res.0);
+}
+#[test]
+fn percent_5() {
+    let res = calculate("-50%").unwrap();
+    assert_eq!(-0.5f64, res.0);
+}
+#[test]
+fn percent_6() {
+    let res = calculate("10 + 50 % von (2 mal 10)").unwrap();
+    assert_eq!(20f64, res.0);
+}
+#[test]
+fn percent_err() {
+    assert!(calculate("% 5").is_err());
+    assert!(calculate("5 prozent 5").is_err());
+}
+
+#[test]
+fn integer_1() {
+    let res = calculate("7 mod 3").unwrap();
+    assert_eq!(1f64, res.0);
+    assert_eq!("(7 mod 3)", res.1);
+}
+#[test]
+fn integer_2() {
+    let res = calculate("-7 % 3").unwrap();
+    assert_eq!(2f64, res.0);
+}
+#[test]
+fn integer_3() {
+    let res = calculate("7 div 3 plus 1").unwrap();
+    assert_eq!(3f64, res.0);
+}
+#[test]
+fn integer_4() {
+    let res = calculate("2 * 5!").unwrap();
+    assert_eq!(240f64, res.0);
+    let res = calculate("drei Fakultät").unwrap();
+    assert_eq!(6f64, res.0);
+    let res = calculate("-3!").unwrap();
+    assert_eq!(-6f64, res.0);
+}
+#[test]
+fn integer_5() {
+    let res = calculate("ggT(12; 18) + kgV(4; 6; 10)").unwrap();
+    assert_eq!(66f64, res.0);
+    assert_eq!("(ggT(12;18)+kgV(4;6;10))", res.1);
+}
+#[test]
+fn integer_6() {
+    let res = calculate("20!").unwrap();
+    assert_eq!(2_432_902_008_176_640_000f64, res.0);
+}
+#[test]
+fn integer_err() {
+    assert!(calculate("21!").is_err());
+    assert!(calculate("(1/2)!").is_err());
+    assert!(calculate("(-1)!").is_err());
+    assert!(calculate("7 mod 0").is_err());
+    assert!(calculate("ggT(1/2; 4)").is_err());
+    assert!(calculate("ggT(4)").is_err());
+    assert!(calculate("ggT 4").is_err());
+    assert!(calculate("1 / 0").is_err());
+}
+
+#[test]
+fn programmer_1() {
+    let res = calculate("0x1F + 0b1010 + 0o17").unwrap();
+    assert_eq!(56f64, res.0);
+}
+#[test]
+fn programmer_2() {
+    let res = calculate("1F hex mal 2").unwrap();
+    assert_eq!(62f64, res.0);
+    let res = calculate("12durch3").unwrap();
+    assert_eq!(4f64, res.0);
+}
+#[test]
+fn programmer_3() {
+    let res = calculate("0xF0 & 0x3C | 1").unwrap();
+    assert_eq!(49f64, res.0);
+    let res = calculate("6 xor 3").unwrap();
+    assert_eq!(5f64, res.0);
+}
+#[test]
+fn programmer_4() {
+    let res = calculate("1 << 4 + 1").unwrap();
+    assert_eq!(32f64, res.0);
+    let res = calculate("256 >> 4").unwrap();
+    assert_eq!(16f64, res.0);
+}
+#[test]
+fn programmer_5() {
+    let res = calculate("nicht 0").unwrap();
+    assert_eq!(-1f64, res.0);
+    let res = calculate("nicht 5 & 7").unwrap();
+    assert_eq!(2f64, res.0);
+}
+#[test]
+fn programmer_format() {
+    use super::settings::{Settings, Base};
+    use super::format::format_number;
+
+    let mut settings = Settings::new();
+    settings.base = Base::Hexadecimal;
+    assert_eq!("0x1F", format_number(31f64, &settings));
+    assert_eq!("0xFFFFFFFFFFFFFFFF", format_number(-1f64, &settings));
+    settings.base = Base::Binary;
+    assert_eq!("0b1010", format_number(10f64, &settings));
+    settings.base = Base::Octal;
+    assert_eq!("0o17", format_number(15f64, &settings));
+}
+#[test]
+fn programmer_err() {
+    assert!(calculate("0x").is_err());
+    assert!(calculate("0b12").is_err());
+    assert!(calculate("1 < 2").is_err());
+    assert!(calculate("1 << 64").is_err());
+    assert!(calculate("(1/2) & 1").is_err());
+    // Ohne Ziffer ist `0x` die Null und ein Wort
+    match calculate("0x") {
+        Err(super::Error::TokenStream(_, errors)) => assert_eq!("unbekanntes Wort", errors[0].msg()),
+        _ => panic!("Expected word error")
+    }
+    assert_eq!(255f64, calculate("0xff").unwrap().0);
+}
+
+#[test]
+fn format_notation() {
+    use super::settings::{Notation, Grouping};
+    use super::format::format_decimal;
+
+    assert_eq!("2", format_decimal(2f64, Notation::Shortest, Grouping::None));
+    assert_eq!("0.1", format_decimal(0.1f64, Notation::Shortest, Grouping::None));
+    assert_eq!("2.00000000", format_decimal(2f64, Notation::Fixed(8), Grouping::None));
+    assert_eq!("3.142", format_decimal(std::f64::consts::PI, Notation::Significant(4), Grouping::None));
+    assert_eq!("10.0", format_decimal(9.996f64, Notation::Significant(3), Grouping::None));
+    assert_eq!("1235", format_decimal(1234.6f64, Notation::Significant(4), Grouping::None));
+    assert_eq!("0.00123", format_decimal(0.0012345f64, Notation::Significant(3), Grouping::None));
+    assert_eq!("1.235e3", format_decimal(1234.6f64, Notation::Scientific(3), Grouping::None));
+    assert_eq!("12.346e3", format_decimal(12345.6f64, Notation::Engineering(3), Grouping::None));
+    assert_eq!("1.000e3", format_decimal(999.9999f64, Notation::Engineering(3), Grouping::None));
+    assert_eq!("-123.000e-6", format_decimal(-0.000123f64, Notation::Engineering(3), Grouping::None));
+}
+#[test]
+fn format_grouping() {
+    use super::settings::{Notation, Grouping};
+    use super::format::format_decimal;
+
+    assert_eq!("1.234.567,89", format_decimal(1234567.89f64, Notation::Fixed(2), Grouping::German));
+    assert_eq!("1,234,567.89", format_decimal(1234567.89f64, Notation::Fixed(2), Grouping::International));
+    assert_eq!("-123.456", format_decimal(-123456f64, Notation::Shortest, Grouping::German));
+    assert_eq!("1,2e3", format_decimal(1234f64, Notation::Scientific(1), Grouping::German));
+}
+#[test]
+fn format_configure() {
+    use super::settings::{Settings, Notation, Grouping};
+    use super::format::configure;
+
+    let mut settings = Settings::new();
+    assert_eq!("2", calculate_output("1 + 1", &settings).unwrap());
+    assert!(configure(&mut settings, "genauigkeit 4").is_ok());
+    assert_eq!(Notation::Significant(4), settings.notation);
+    assert!(configure(&mut settings, "gruppierung deutsch").is_ok());
+    assert_eq!(Grouping::German, settings.grouping);
+    assert!(configure(&mut settings, "wissenschaftlich").is_ok());
+    assert_eq!(Notation::Scientific(6), settings.notation);
+    assert!(configure(&mut settings, "nachkomma").is_err());
+    assert!(configure(&mut settings, "genauigkeit 99").is_err());
+    assert!(configure(&mut settings, "genauigkeit 0").is_err());
+    assert_eq!(Notation::Scientific(6), settings.notation);
+    assert!(configure(&mut settings, "gruppierung").is_err());
+}
+
+fn unit(input: &str) -> String {
+    let settings = super::Settings::new();
+    let (value, _) = calculate_value(input, &settings).unwrap();
+    super::format::format_value(&value, &settings)
+}
+#[test]
+fn unit_1() {
+    assert_eq!("3200 mm", unit("3 Meter plus 20 Zentimeter in Millimeter"));
+}
+#[test]
+fn unit_2() {
+    assert_eq!("2.5 km/h", unit("5 km / 2 h"));
+    assert_eq!("0.6944444444444444 m/s", unit("5 km / 2 h in m/s"));
+}
+#[test]
+fn unit_3() {
+    assert_eq!("90 N", unit("10 kg mal 9 m durch (1 s mal 1 s) in Newton"));
+    assert_eq!("6 m²", unit("2 m * 3 m"));
+    assert_eq!("0.5 1/s", unit("1 / 2 s"));
+}
+#[test]
+fn unit_4() {
+    let res = calculate("100 km / 1 m").unwrap();
+    assert_eq!(100_000f64, res.0);
+}
+#[test]
+fn unit_5() {
+    assert_eq!("110 kg", unit("100 kg + 10%"));
+    assert_eq!("-3 m", unit("-3 m"));
+}
+#[test]
+fn unit_err() {
+    assert!(calculate("3 m").is_err());
+    assert!(calculate("3 m + 2 s").is_err());
+    assert!(calculate("3 m in kg").is_err());
+    assert!(calculate("3 m in 2").is_err());
+    assert!(calculate("3 m s").is_err());
+    assert!(calculate("ggT(2 m; 4)").is_err());
+}
+
+fn money(input: &str, settings: &super::Settings) -> String {
+    let (value, _) = calculate_value(input, settings).unwrap();
+    super::format::format_value(&value, settings)
+}
+fn rates() -> super::Settings {
+    let mut settings = super::Settings::new();
+    settings.rates = Some(super::currency::RateTable::parse("# 1 Einheit in EUR\nbasis EUR\nUSD 0,8\nJPY 0,00625").unwrap());
+    settings
+}
+#[test]
+fn currency_1() {
+    let settings = super::Settings::new();
+    assert_eq!("16,49 €", money("12,50 € plus 3,99 €", &settings));
+    assert_eq!("0,30 €", money("0,10 € + 0,20 €", &settings));
+    assert_eq!("-2,50 $", money("-2,5 Dollar", &settings));
+    // Beträge mit mehr als 15 Stellen bleiben exakt
+    assert_eq!("12345678901234567,89 €", money("12345678901234567,89 EUR", &settings));
+    assert_eq!("-12345678901234567,89 €", money("-12345678901234567,89 €", &settings));
+    assert_eq!("123456789012345678,00 €", money("123456789012345678 €", &settings));
+    assert_eq!("12345678901234567,90 €", money("12345678901234567,89 € + 0,01 €", &settings));
+}
+#[test]
+fn currency_2() {
+    let settings = super::Settings::new();
+    assert_eq!("3,33 €", money("10 € / 3", &settings));
+    assert_eq!("119,00 €", money("100 € + 19%", &settings));
+    assert_eq!("19,00 €", money("19 % von 100 €", &settings));
+    assert_eq!("1235 ¥", money("1234,5 Yen", &settings));
+    let res = calculate("10 € / 4 €").unwrap();
+    assert_eq!(2.5f64, res.0);
+}
+#[test]
+fn currency_rounding() {
+    let mut settings = super::Settings::new();
+    assert_eq!("0,13 €", money("0,125 €", &settings));
+    settings.rounding = super::settings::Rounding::Bankers;
+    assert_eq!("0,12 €", money("0,125 €", &settings));
+    assert_eq!("0,14 €", money("0,135 €", &settings));
+    super::currency::configure(&mut settings, "stellen EUR 3").unwrap().unwrap();
+    assert_eq!("0,125 €", money("0,125 €", &settings));
+}
+#[test]
+fn currency_scale() {
+    use super::decimal::Decimal;
+
+    let settings = super::Settings::new();
+    let tiny = format!("0,{}1 €", "0".repeat(59));
+    assert_eq!("0,00 €", money(tiny.as_str(), &settings));
+    assert_eq!("0,00 €", money(format!("{} * 3", tiny).as_str(), &settings));
+    assert_eq!("0,33 €", money(format!("1,{}1 € / 3", "0".repeat(40)).as_str(), &settings));
+    // Mehr als 18 Stellen hinter dem Komma werden gerundet
+    assert_eq!(Some(Decimal::new(2, 18)), Decimal::parse("0,0000000000000000015"));
+    assert_eq!(Some(Decimal::new(2, 18)), Decimal::parse("0,0000000000000000025"));
+    assert_eq!(Some(Decimal::new(3, 18)), Decimal::parse("0,00000000000000000250001"));
+}
+#[test]
+fn currency_rates() {
+    let settings = rates();
+    assert_eq!("80,00 €", money("100 $ in €", &settings));
+    assert_eq!("80 ¥", money("0,5 € in Yen", &settings));
+    assert_eq!("18,00 €", money("10 € + 10 USD", &settings));
+    assert!(calculate_value("10 € in CHF", &settings).is_err());
+}
+#[test]
+fn currency_err() {
+    let settings = super::Settings::new();
+    assert!(calculate_value("10 € + 10 $", &settings).is_err());
+    assert!(calculate_value("10 € in $", &settings).is_err());
+    assert!(calculate_value("10 € + 5", &settings).is_err());
+    assert!(calculate_value("10 € * 2 €", &settings).is_err());
+    assert!(calculate_value("10 € + 2 m", &settings).is_err());
+    assert!(calculate_value("10 €", &settings).is_ok());
+    assert!(calculate("10 €").is_err());
+    assert!(calculate("1,").is_err());
+    assert!(super::currency::RateTable::parse("XYZ 1").is_err());
+    assert!(super::currency::RateTable::parse("USD null").is_err());
+}
+
+#[test]
+fn date_1() {
+    assert_eq!("Samstag, 07.11.2026", unit("17.10.2026 plus 3 Wochen"));
+    assert_eq!("Donnerstag, 01.01.2026", unit("1.1.2026"));
+    assert_eq!("Sonntag, 29.02.2032", unit("28.2.2032 + 1 Tag"));
+    assert_eq!("Mittwoch, 31.12.2025", unit("1.1.2026 - 24 h"));
+}
+#[test]
+fn date_2() {
+    assert_eq!("357 d", unit("Tage zwischen 1.1.2026 und 24.12.2026"));
+    assert_eq!("51 Wo", unit("Wochen zwischen 1.1.2026 und 24.12.2026"));
+    assert_eq!("-2 d", unit("zwischen 3.1.2026 und 1.1.2026"));
+    assert_eq!("1 d", unit("heute plus 1 Tag minus heute"));
+    // `%` als Modulo vor und nach einem `zwischen`
+    assert_eq!(10f64, calculate("(zwischen 1 und 10) + 7 % 3").unwrap().0);
+    assert_eq!(10f64, calculate("7 % 3 + (zwischen 1 und 10)").unwrap().0);
+}
+#[test]
+fn duration_1() {
+    assert_eq!("10 h", unit("2 Stunden 30 Minuten mal 4"));
+    assert_eq!("320 cm", unit("3 m 20 cm in cm"));
+}
+#[test]
+fn date_err() {
+    let settings = super::Settings::new();
+    assert!(calculate_value("29.2.2025", &settings).is_err());
+    assert!(calculate_value("17.10", &settings).is_err());
+    assert!(calculate_value("1.1.2026 + 1.1.2026", &settings).is_err());
+    assert!(calculate_value("1.1.2026 + 1,5 Tage", &settings).is_err());
+    assert!(calculate_value("1.1.2026 * 2", &settings).is_err());
+    assert!(calculate_value("1.1.2026 + 3 m", &settings).is_err());
+    assert!(calculate_value("zwischen 1.1.2026", &settings).is_err());
+    assert!(calculate_value("1 und 2", &settings).is_err());
+    assert!(calculate_value("2 h 30 kg", &settings).is_err());
+    assert!(calculate("1.1.2026").is_err());
+}
+
+#[test]
+fn compare_1() {
+    assert_eq!("wahr", unit("ist 3 mal 4 größer als 10?"));
+    assert_eq!("falsch", unit("3 kleiner als 2"));
+    assert_eq!("wahr", unit("3 größer 2"));
+    assert_eq!("wahr", unit("3 kleiner gleich 3"));
+    assert_eq!("wahr", unit("0,1 + 0,2 = 0,3"));
+    assert_eq!("wahr", unit("5 ≠ 6"));
+    assert_eq!("falsch", unit("5 >= 6"));
+}
+#[test]
+fn compare_2() {
+    assert_eq!("wahr", unit("3 m > 200 cm"));
+    assert_eq!("falsch", unit("10 € ≤ 5 €"));
+    assert_eq!("wahr", unit("1.1.2026 < 24.12.2026"));
+    assert_eq!("wahr", unit("dreiundzwanzig gleich 23"));
+}
+#[test]
+fn boolean_1() {
+    let res = calculate_value("1 < 2 und 3 > 4 oder 5 ≠ 6", &super::Settings::new()).unwrap();
+    assert_eq!("(((1<2) und (3>4)) oder (5≠6))", res.1);
+    assert_eq!("wahr", unit("nicht 3 > 5"));
+    assert_eq!("falsch", unit("nicht 1 < 2 und 2 < 3"));
+    assert_eq!("wahr", unit("nicht (1 > 2) und (2 < 3)"));
+    assert_eq!("357 d", unit("Tage zwischen 1.1.2026 und 24.12.2026"));
+    let res = calculate("nicht 5 & 7").unwrap();
+    assert_eq!(2f64, res.0);
+}
+#[test]
+fn boolean_err() {
+    let settings = super::Settings::new();
+    assert!(calculate_value("2 und 3", &settings).is_err());
+    assert!(calculate_value("3 < 2 < 1", &settings).is_err());
+    assert!(calculate_value("3 m > 2 kg", &settings).is_err());
+    assert!(calculate_value("(1 < 2) + 1", &settings).is_err());
+    assert!(calculate_value("1 ? 2", &settings).is_err());
+    assert!(calculate("1 < 2").is_err());
+}
+
+#[test]
+fn condition_1() {
+    let res = calculate("wenn 3 größer 0 dann 3 sonst minus 3").unwrap();
+    assert_eq!(3f64, res.0);
+    assert_eq!("(wenn (3>0) dann 3 sonst (0-3))", res.1);
+    let res = calculate("wenn (0-3) größer 0 dann (0-3) sonst minus (0-3)").unwrap();
+    assert_eq!(3f64, res.0);
+}
+#[test]
+fn condition_2() {
+    let res = calculate("2 * (wenn 1 < 2 dann 10 sonst 20) + 1").unwrap();
+    assert_eq!(21f64, res.0);
+    let res = calculate("1 + wenn 1 > 2 dann 10 sonst 20 + 1").unwrap();
+    assert_eq!(22f64, res.0);
+    let res = calculate("wenn 1 > 0 dann wenn 2 > 3 dann 1 sonst 2 sonst 3").unwrap();
+    assert_eq!(2f64, res.0);
+}
+#[test]
+fn condition_lazy() {
+    let res = calculate("wenn 1 > 0 dann 1 sonst 1 / 0").unwrap();
+    assert_eq!(1f64, res.0);
+    assert!(calculate("wenn 1 < 0 dann 1 sonst 1 / 0").is_err());
+}
+#[test]
+fn condition_err() {
+    assert!(calculate("wenn 1 > 0 dann 5").is_err());
+    assert!(calculate("wenn 1 > 2 sonst 3").is_err());
+    assert!(calculate("1 dann 2").is_err());
+    assert!(calculate("wenn 1 dann 2 sonst 3").is_err());
+    assert!(calculate("(wenn 1 > 2 dann 3) sonst 4").is_err());
+}
+
+#[test]
+fn steps_1() {
+    let settings = super::Settings::new();
+    let steps = calculate_steps("1 + (minus 3 mal 6) durch 2", &settings, super::Style::Unicode).unwrap();
+    assert_eq!("1 + (-3) × 6 ÷ 2 → 1 + (-18) ÷ 2 → 1 + (-9) → -8", steps.join(" → "));
+    let steps = calculate_steps("(1 - 2) - (3 - 4)", &settings, super::Style::Unicode).unwrap();
+    assert_eq!("1 - 2 - (3 - 4) → (-1) - (3 - 4) → (-1) - (-1) → 0", steps.join(" → "));
+    let steps = calculate_steps("wenn 3 > 2 dann 50 + 10% sonst 1 / 0", &settings, super::Style::Unicode).unwrap();
+    assert_eq!("wenn 3 > 2 dann 50 + 10% sonst 1 ÷ 0 → wenn wahr dann 50 + 10% sonst 1 ÷ 0 → wenn wahr dann 55 sonst 1 ÷ 0 → 55", steps.join(" → "));
+}
+#[test]
+fn steps_2() {
+    let settings = super::Settings::new();
+    let steps = calculate_steps("21 mal (2 minus 3,5)", &settings, super::Style::Words).unwrap();
+    // Zahlen mit Komma und ab einer Million bleiben Ziffern
+    assert_eq!("einundzwanzig mal auf zwei minus 3,5 zu → einundzwanzig mal auf minus 1,5 zu → minus 31,5", steps.join(" → "));
+    let steps = calculate_steps("1001 + 1000000", &settings, super::Style::Words).unwrap();
+    assert_eq!("eintausendeins plus 1000000 → 1001001", steps.join(" → "));
+}
+
+#[test]
+fn pretty_1() {
+    let mut settings = super::Settings::new();
+    assert_eq!("1 + (-3) × 6 ÷ 2", calculate_pretty("1 + (minus 3 mal 6) durch 2", &settings).unwrap());
+    assert_eq!("1 - (2 - 3) - 4", calculate_pretty("((1 - (2 - 3)) - 4)", &settings).unwrap());
+    assert_eq!("-(2 + 3)!", calculate_pretty("-((2 + 3)!)", &settings).unwrap());
+    assert_eq!("wenn 1 < 2 und nicht (3 ≥ 4) dann 1 sonst 2", calculate_pretty("wenn (1 < 2) und nicht (3 >= 4) dann 1 sonst 2", &settings).unwrap());
+    settings.style = super::Style::Ascii;
+    assert_eq!("(1 + 2) * 3 / 4", calculate_pretty("(1 + 2) mal 3 durch 4", &settings).unwrap());
+    assert_eq!("12,5 EUR + 3 promille", calculate_pretty("12,50 € + 3 ‰", &settings).unwrap());
+    settings.style = super::Style::Words;
+    assert_eq!("auf eins plus zwei zu mal drei", calculate_pretty("(1 + 2) * 3", &settings).unwrap());
+}
+#[test]
+fn pretty_roundtrip() {
+    let inputs = [
+        "1 + (minus 3 mal 6) durch 2",
+        "eins plus (minus drei mal 6) durch 2 minus (drei mal 4)",
+        "((1 - (2 - 3)) - 4)",
+        "1 / (2 / 3) / 4",
+        "-(2 + 3)",
+        "(-3) * 4! + 5%",
+        "250 plus 19 Prozent",
+        "19 % von 250",
+        "7 mod (3 div 2)",
+        "nicht 5 & 7 | 1 << 2",
+        "ggT(12; 18) + kgV(4; 6; 10)",
+        "3 Meter plus 20 Zentimeter in Millimeter",
+        "2 Stunden 30 Minuten mal 4",
+        "12,50 € plus 3,99 €",
+        "17.10.2026 plus 3 Wochen",
+        "Tage zwischen 1.1.2026 und 24.12.2026",
+        "(1 < 2 und 3 > 4) oder 5 ≠ 6",
+        "2 * (wenn 1 < 2 dann 10 sonst 20) + 1",
+        "wenn 1 > 0 dann wenn 2 > 3 dann 1 sonst 2 sonst 3",
+        "1,5 + 2",
+        "-0,25 * 4",
+        "2000000 * 3",
+        "999999 + 1",
+        "-1000000 - 1",
+    ];
+    let mut settings = super::Settings::new();
+    for style in [super::Style::Ascii, super::Style::Unicode, super::Style::Words] {
+        settings.style = style;
+        for input in inputs.iter() {
+            let expected = calculate_value(input, &settings).unwrap().1;
+            let printed = calculate_pretty(input, &settings).unwrap();
+            let reparsed = calculate_value(printed.as_str(), &settings);
+            assert!(reparsed.is_ok(), "{:?}: {} -> {}", style, input, printed);
+            assert_eq!(expected, reparsed.unwrap().1, "{:?}: {} -> {}", style, input, printed);
+        }
+    }
+}
+
+#[test]
+fn export_latex() {
+    let settings = super::Settings::new();
+    let latex = |input| calculate_export(input, &settings, super::Export::Latex).unwrap();
+    assert_eq!("1 + \\frac{\\left(-3\\right) \\cdot 6}{2}", latex("1 + (minus 3 mal 6) durch 2"));
+    assert_eq!("\\left(1 + 2\\right) \\cdot 3 - 4{,}5", latex("(1 + 2) * 3 - 4,5"));
+    assert_eq!("\\operatorname{ggT}\\left(12; 18\\right) \\leq 5\\,\\%", latex("ggT(12; 18) ≤ 5%"));
+    assert_eq!("12{,}5\\,\\text{\\$} \\text{ in } \\text{€}", latex("12,50 $ in €"));
+}
+#[test]
+fn export_mathml() {
+    let settings = super::Settings::new();
+    let mathml = calculate_export("1 / (2 + 3) < 4", &settings, super::Export::MathMl).unwrap();
+    assert_eq!("<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow><mfrac><mn>1</mn><mrow><mn>2</mn><mo>+</mo><mn>3</mn></mrow></mfrac><mo>&lt;</mo><mn>4</mn></mrow></math>", mathml);
+    let mathml = calculate_export("-(2 mal 3 m)", &settings, super::Export::MathMl).unwrap();
+    assert_eq!("<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow><mo>-</mo><mrow><mo>(</mo><mrow><mn>2</mn><mo>⋅</mo><mrow><mn>3</mn><mspace width=\"0.2em\"/><mi mathvariant=\"normal\">m</mi></mrow></mrow><mo>)</mo></mrow></mrow></math>", mathml);
+}
+
+#[test]
+fn tree_ascii() {
+    let settings = super::Settings::new();
+    let tree = calculate_tree("1 + (minus 3 mal 6) durch 2", &settings, super::Dump::Ascii).unwrap();
+    let expected = [
+        "+  0..27 \"1 + (minus 3 mal 6) durch 2\"  = -8",
+        "├── 1  0..1 \"1\"  = 1",
+        "└── ÷  4..27 \"(minus 3 mal 6) durch 2\"  = -9",
+        "    ├── ×  5..18 \"minus 3 mal 6\"  = -18",
+        "    │   ├── -3  5..12 \"minus 3\"  = -3",
+        "    │   └── 6  17..18 \"6\"  = 6",
+        "    └── 2  26..27 \"2\"  = 2",
+        "",
+    ];
+    assert_eq!(expected.join("\n"), tree);
+    let tree = calculate_tree("wenn 1 > 2 dann 1 / 0 sonst 5", &settings, super::Dump::Ascii).unwrap();
+    assert!(tree.contains("├── ÷  16..21 \"1 / 0\"  = nicht berechnet"));
+    let tree = calculate_tree("1 + 2 / 0", &settings, super::Dump::Ascii).unwrap();
+    assert!(tree.ends_with("Fehler bei 4..9 \"2 / 0\": Teilen durch Null nicht möglich\n"));
+}
+
+#[test]
+fn tree_dot() {
+    let settings = super::Settings::new();
+    let tree = calculate_tree("2 * 3!", &settings, super::Dump::Dot).unwrap();
+    let expected = [
+        "digraph ParseTree {",
+        "    node [shape=box, fontname=\"monospace\"];",
+        "    n3 [label=\"×\\n0..6 \\\"2 * 3!\\\"\\n= 12\"];",
+        "    n3 -> n0;",
+        "    n3 -> n2;",
+        "    n0 [label=\"2\\n0..1 \\\"2\\\"\\n= 2\"];",
+        "    n2 [label=\"!\\n4..6 \\\"3!\\\"\\n= 6\"];",
+        "    n2 -> n1;",
+        "    n1 [label=\"3\\n4..5 \\\"3\\\"\\n= 3\"];",
+        "}",
+        "",
+    ];
+    assert_eq!(expected.join("\n"), tree);
+}
+
+#[test]
+fn tokens_1() {
+    let settings = super::Settings::new();
+    let tokens = super::stream::dump_tokens("dreiundzwanzig mal 2 m", &settings);
+    let expected = [
+        "Zeichen:",
+        "    0..14  Buchstaben   \"dreiundzwanzig\"",
+        "   14..15  Leerzeichen  \" \"",
+        "   15..18  Buchstaben   \"mal\"",
+        "   18..19  Leerzeichen  \" \"",
+        "   19..20  Ziffern      \"2\"",
+        "   20..21  Leerzeichen  \" \"",
+        "   21..22  Buchstaben   \"m\"",
+        "Wörter:",
+        "    0..14  dreiundzwanzig       Number(3) 0..4, Und 4..7, Zwanzig 7..14",
+        "   15..18  mal                  Multiply",
+        "   21..22  m                    Einheit m",
+        "Token:",
+        "    0..14  Number(23)           \"dreiundzwanzig\"",
+        "   15..18  Op(Multiply)         \"mal\"",
+        "   19..20  Number(2)            \"2\"",
+        "   21..22  Unit(m)              \"m\"",
+        "",
+    ];
+    assert_eq!(expected.join("\n"), tokens);
+    let tokens = super::stream::dump_tokens("1 + xyz", &settings);
+    assert!(tokens.contains("    4..7   xyz                  unbekannt\n"));
+    assert!(tokens.contains("unbekanntes Wort"));
+}
+
+#[test]
+fn spans_1() {
+    let settings = super::Settings::new();
+    let input = "1 + 2 durch (3 - 3)";
+    let tree = super::parse(input, &settings).unwrap();
+    let node = match &tree {
+        super::ParseTree::Node(node) => node,
+        _ => panic!("Expected node")
+    };
+    assert_eq!("1", node.left().source(input));
+    assert_eq!("2 durch (3 - 3)", node.right().source(input));
+    assert_eq!("+", &input[node.range().start..node.range().end]);
+    match calculate(input) {
+        Err(super::Error::TokenStream(_, errors)) => {
+            assert_eq!((4, 19), (errors[0].range().start, errors[0].range().end));
+        }
+        _ => panic!("Expected division error")
+    }
+    // Teilwort eines zusammengesetzten Zahlworts
+    match calculate("1 + dreizehnzig") {
+        Err(super::Error::TokenStream(_, errors)) => {
+            assert_eq!((12, 15), (errors[0].range().start, errors[0].range().end));
+        }
+        _ => panic!("Expected unknown word")
+    }
+}
+
+#[test]
+fn overflow_1() {
+    match calculate("1 + 99999999999999999999") {
+        Err(super::Error::TokenStream(_, errors)) => {
+            assert_eq!((4, 24), (errors[0].range().start, errors[0].range().end));
+            assert_eq!("Zahl zu groß", errors[0].msg());
+        }
+        _ => panic!("Expected overflow")
+    }
+    let res = calculate("9223372036854775807").unwrap();
+    assert_eq!(9223372036854775807f64, res.0);
+    let res = calculate("0xFFFFFFFFFFFFFFFF").unwrap();
+    assert_eq!(-1f64, res.0);
+    assert!(calculate("0x10000000000000000").is_err());
+}
+#[test]
+fn overflow_2() {
+    // `0x8000000000000000` ist `i64::MIN`, negiert passt es nicht in 64 Bit
+    match calculate("-0x8000000000000000") {
+        Err(super::Error::TokenStream(_, errors)) => {
+            assert_eq!((0, 19), (errors[0].range().start, errors[0].range().end));
+            assert_eq!("Zahl zu groß", errors[0].msg());
+        }
+        _ => panic!("Expected overflow")
+    }
+    assert!(calculate("nicht -0x8000000000000000 + 1").is_err());
+    assert!(calculate("2 * -0x8000000000000000").is_err());
+    assert_eq!(9223372036854775808f64, calculate("0 - 0x8000000000000000").unwrap().0);
+
+    let mut settings = super::Settings::new();
+    settings.exact = true;
+    for input in ["ggT(0-9223372036854775807-1; 0)", "ggT(0-9223372036854775807-1; -1)", "kgV(0-9223372036854775807-1; 3)"] {
+        match calculate_output(input, &settings) {
+            Err(super::Error::TokenStream(_, errors)) => assert_eq!("Zahl zu groß", errors[0].msg()),
+            _ => panic!("Expected overflow for {}", input)
+        }
+    }
+    assert_eq!("9223372036854775807", calculate_output("ggT(0-9223372036854775807; 0)", &settings).unwrap());
+}
+#[test]
+fn exact_1() {
+    let mut settings = super::Settings::new();
+    assert_eq!("9007199254740992", calculate_output("9007199254740993 + 0", &settings).unwrap());
+    settings.exact = true;
+    assert_eq!("9007199254740993", calculate_output("9007199254740993 + 0", &settings).unwrap());
+    assert_eq!("2432902008176640000", calculate_output("20!", &settings).unwrap());
+    assert_eq!("2", calculate_output("-7 mod 3", &settings).unwrap());
+    assert_eq!("4", calculate_output("ggT(12; 8)", &settings).unwrap());
+    // Nicht ganzzahlig, es wird mit f64 gerechnet
+    assert_eq!("3.5", calculate_output("7 / 2", &settings).unwrap());
+    assert_eq!("1.5", calculate_output("1,5", &settings).unwrap());
+    match calculate_output("9223372036854775807 + 1", &settings) {
+        Err(super::Error::TokenStream(_, errors)) => {
+            assert_eq!((0, 23), (errors[0].range().start, errors[0].range().end));
+            assert_eq!("Zahl zu groß", errors[0].msg());
+        }
+        _ => panic!("Expected overflow")
+    }
+}
+#[test]
+fn non_finite_1() {
+    let mut settings = super::Settings::new();
+    let big = ["99999999999999999"; 20].join(" * ");
+    let nan = format!("{} - {}", big, big);
+    match calculate_output(big.as_str(), &settings) {
+        Err(super::Error::TokenStream(_, errors)) => assert_eq!("Ergebnis unendlich", errors[0].msg()),
+        _ => panic!("Expected infinity error")
+    }
+    assert!(calculate_output(nan.as_str(), &settings).is_err());
+    settings.non_finite = super::settings::NonFinite::Display;
+    assert_eq!("unendlich", calculate_output(big.as_str(), &settings).unwrap());
+    assert_eq!("-unendlich", calculate_output(format!("-({})", big).as_str(), &settings).unwrap());
+    assert_eq!("nicht definiert", calculate_output(nan.as_str(), &settings).unwrap());
+    assert!(calculate_output("1 / 0", &settings).is_err());
+}
+#[test]
+fn negative_zero() {
+    let mut settings = super::Settings::new();
+    assert_eq!("0", calculate_output("-0", &settings).unwrap());
+    assert_eq!("0", calculate_output("-0,0", &settings).unwrap());
+    assert_eq!("0", calculate_output("0 * (-1)", &settings).unwrap());
+    settings.notation = super::settings::Notation::Fixed(8);
+    assert_eq!("0.00000000", calculate_output("-0,000000001", &settings).unwrap());
+    assert_eq!("null", super::words::number(-0f64));
+    assert!(calculate("1 / (-0)").is_err());
+}
+#[test]
+fn limits_1() {
+    let mut settings = super::Settings::new();
+    // Klammern um den ganzen Ausdruck ergeben keine weitere Ebene
+    let nested = format!("{}1{}", "auf ".repeat(900), " zu".repeat(900));
+    assert_eq!("1", calculate_output(nested.as_str(), &settings).unwrap());
+    let deep = format!("{}1{}", "(1 + ".repeat(195), ")".repeat(195));
+    assert_eq!("196", calculate_output(deep.as_str(), &settings).unwrap());
+    assert!(calculate_steps(deep.as_str(), &settings, super::Style::Ascii).is_ok());
+    assert!(calculate_tree(deep.as_str(), &settings, super::Dump::Ascii).is_ok());
+    let deep = format!("{}1{}", "(1 + ".repeat(300), ")".repeat(300));
+    assert!(matches!(calculate_output(deep.as_str(), &settings), Err(super::Error::TooDeep(200))));
+    let long = "1".repeat(100_001);
+    assert!(matches!(calculate_output(long.as_str(), &settings), Err(super::Error::InputTooLong(100_000))));
+    let many = ["1"; 25_001].join("+");
+    assert!(matches!(calculate_output(many.as_str(), &settings), Err(super::Error::TooManyTokens(50_000))));
+
+    settings.limits.depth = 1_000;
+    settings.limits.steps = 100;
+    let wide = format!("ggT({})", ["(1 + 1)"; 40].join("; "));
+    assert!(matches!(calculate_output(wide.as_str(), &settings), Err(super::Error::TooManySteps(100))));
+}
+#[test]
+fn linear_1() {
+    let settings = super::Settings::new();
+    // Eine lange Summe zählt nur zwei Ebenen und wird ohne Rekursion durchlaufen
+    let long = ["1"; 8_000].join(" + ");
+    let tree = super::parse(long.as_str(), &settings).unwrap();
+    assert_eq!(15_999, tree.size());
+    assert_eq!(long, tree.clone().pretty(super::Style::Ascii));
+    assert_eq!("8000", calculate_output(long.as_str(), &settings).unwrap());
+    assert!(calculate_export(long.as_str(), &settings, super::Export::Latex).is_ok());
+    // Jeder Knoten zeigt seinen Teilausdruck, die Ausgabe wächst quadratisch
+    let dump = ["1"; 1_000].join(" + ");
+    assert!(calculate_tree(dump.as_str(), &settings, super::Dump::Dot).is_ok());
+    assert!(calculate_tree(dump.as_str(), &settings, super::Dump::Ascii).is_ok());
+    let mut exact = settings.clone();
+    exact.exact = true;
+    assert_eq!("8000", calculate_output(long.as_str(), &exact).unwrap());
+    let bench: String = (1..8_000).map(|i| format!(" {} {}", ["+", "-", "*", "+"][i % 4], i % 100)).collect();
+    assert!(calculate_output(format!("1{}", bench).as_str(), &settings).is_ok());
+    let variable = ["x"; 8_000].join(" + ");
+    assert_eq!("8000 × x", super::calculate_simplified(variable.as_str(), &settings).unwrap());
+    assert_eq!("8000", super::calculate_derivative(variable.as_str(), "x", &settings).unwrap());
+    assert_eq!("x = 1", super::calculate_solutions(format!("{} = 8000", variable).as_str(), &settings).unwrap());
+    let compiled = super::CompiledExpr::compile(variable.as_str(), &["x"], &settings).unwrap().fold();
+    assert_eq!(16_000f64, compiled.evaluate_number(&[2f64]).unwrap());
+    let sum = ["1"; 150].join(" + ");
+    assert_eq!("150", calculate_output(sum.as_str(), &settings).unwrap());
+    assert_eq!("-10", calculate_output("-5! / 20 + 2 * (-2)", &settings).unwrap());
+    assert_eq!("wahr", calculate_output("nicht 3 > 5 und 2 < 3", &settings).unwrap());
+    match calculate_output("(1 + 2)) * (3", &settings) {
+        Err(super::Error::TokenStream(_, errors)) => {
+            assert_eq!("Öffnende Klammer fehlt", errors[0].msg());
+            assert_eq!("Schließende Klammer fehlt", errors[1].msg());
+        }
+        _ => panic!("Expected parenthesis errors")
+    }
+}
+#[test]
+fn lexer_1() {
+    let input = "Größer ÷ 2";
+    let tokens: Vec<_> = super::stream::Lexer::new(input).map(|token| token.range()).map(|range| &input[range.start..range.end]).collect();
+    assert_eq!(vec!["Größer", " ", "÷", " ", "2"], tokens);
+    // Stellen in Bytes, die Fehlermeldung zeigt trotzdem auf das Zeichen
+    match calculate("fünf × × 2") {
+        Err(super::Error::TokenStream(input, errors)) => {
+            assert_eq!("fünf × × 2", input);
+            assert_eq!((6, 8), (errors[0].range().start, errors[0].range().end));
+            let msg = super::Error::TokenStream(input, errors).to_string();
+            assert!(msg.contains("\n\r              ^  "));
+        }
+        _ => panic!("Expected operator error")
+    }
+    assert_eq!(15f64, calculate("Drei MAL Fünf").unwrap().0);
+    assert_eq!(1f64, calculate("wenn 3 KLEINER ALS 4 dann 1 sonst 2").unwrap().0);
+}
+#[test]
+fn compile_1() {
+    let mut settings = super::Settings::new();
+    settings.implicit_multiplication = true;
+    let expr = super::CompiledExpr::compile("3x + 5", &["x"], &settings).unwrap();
+    assert_eq!(5f64, expr.evaluate_number(&[0f64]).unwrap());
+    assert_eq!(20f64, expr.evaluate_number(&[5f64]).unwrap());
+    assert!(expr.evaluate(&[]).is_err());
+    // Ohne Variable bleibt x ein unbekanntes Wort
+    assert!(super::CompiledExpr::compile("3x + 5", &[], &settings).is_err());
+
+    let expr = super::CompiledExpr::compile("2 * 3 * X + wenn y > 0 dann y sonst -y + 10%", &["x", "y"], &settings).unwrap().fold();
+    assert_eq!("6 × x + (wenn y > 0 dann y sonst -y + 10%)", expr.tree().pretty(super::Style::Unicode));
+    assert_eq!(14f64, expr.evaluate_number(&[2f64, 2f64]).unwrap());
+    assert_eq!(14.2f64, expr.evaluate_number(&[2f64, -2f64]).unwrap());
+    // Fehler bleiben bis zur Auswertung erhalten
+    let expr = super::CompiledExpr::compile("x / (1 - 1)", &["x"], &settings).unwrap().fold();
+    match expr.evaluate(&[1f64]) {
+        Err(e) => assert_eq!((0, 11), (e.range().start, e.range().end)),
+        _ => panic!("Expected division error")
+    }
+}
+#[test]
+fn simplify_1() {
+    let mut settings = super::Settings::new();
+    settings.implicit_multiplication = true;
+    let simplify = |input| super::calculate_simplified(input, &settings).unwrap();
+    assert_eq!("4 × x", simplify("2x + 3x - x * 1 + 0"));
+    assert_eq!("0", simplify("x - x"));
+    assert_eq!("3 × x + 2", simplify("(6x + 4) / 2"));
+    assert_eq!("1 ÷ 2", simplify("2x / (4x)"));
+    assert_eq!("2", simplify("(2x + 2) / (x + 1)"));
+    assert_eq!("x ^ 2 + 2 × x × y + y ^ 2", simplify("(x + y)(y + x)"));
+    assert_eq!("x ^ 2 - 1 ÷ x ^ 2", simplify("x * x - x^(-2)"));
+    assert_eq!("x × y ÷ 2 - 2 × x + 3", simplify("3 - 2x + y * x / 2"));
+    assert_eq!("-x - 3", simplify("-(x + 3)"));
+    assert_eq!("2 ÷ (x + 1)", simplify("1 / (x + 1) + 1 / (1 + x)"));
+    // Prozent und Teilen durch Null bleiben erhalten
+    assert_eq!("x + 10%", simplify("x + 10%"));
+    assert_eq!("x ÷ 0", simplify("x / (1 - 1)"));
+    assert_eq!("wenn 2 × x > 2 dann 2 sonst 0", simplify("wenn x + x > 2 dann 1 + 1 sonst y * 0"));
+}
+#[test]
+fn derive_1() {
+    assert_eq!(-4f64, calculate("-2^2").unwrap().0);
+    assert_eq!(512f64, calculate("2 hoch 3 hoch 2").unwrap().0);
+    assert_eq!(1f64, calculate("kosinus von 0").unwrap().0);
+    let mut settings = super::Settings::new();
+    settings.implicit_multiplication = true;
+    let derive = |input, variable| super::calculate_derivative(input, variable, &settings).unwrap();
+    assert_eq!("x ^ 2 × cos(x) + 2 × x × sin(x)", derive("x hoch 2 mal sinus von x", "x"));
+    assert_eq!("9 × x ^ 2 - 2", derive("3x^3 - 2x + 7", "x"));
+    assert_eq!("-1 ÷ x ^ 2", derive("1 / x", "x"));
+    assert_eq!("2 × x ÷ (x ^ 2 + 1)", derive("ln(x^2 + 1)", "x"));
+    assert_eq!("2 ^ x × ln(2)", derive("2^x", "x"));
+    assert_eq!("2 × x × y", derive("x y^2", "y"));
+    // Konstante Teilausdrücke werden nicht abgeleitet, auch wenn dies nicht möglich wäre
+    assert_eq!("0", derive("5", "x"));
+    assert_eq!("0", derive("x^2", "y"));
+    assert_eq!("0", derive("x + 1 - x", "x"));
+    assert_eq!("1", derive("ggT(4; 6) mod 3 + x", "x"));
+    assert_eq!("11 ÷ 10", derive("x + 10%", "x"));
+    match super::calculate_derivative("x! + 1", "x", &settings) {
+        Err(super::Error::TokenStream(_, errors)) => assert_eq!("Ableitung mit \"!\" nicht möglich", errors[0].msg()),
+        _ => panic!("Expected derivative error")
+    }
+    match super::calculate_derivative("x mod 2", "x", &settings) {
+        Err(super::Error::TokenStream(_, errors)) => assert_eq!("Ableitung mit \"mod\" nicht möglich", errors[0].msg()),
+        _ => panic!("Expected derivative error")
+    }
+    assert!(matches!(super::calculate_derivative("x^2", "q", &settings), Err(super::Error::Evaluate(_))));
+    // Ein Komma vor einer Ziffer gehört zur Zahl
+    assert_eq!(Some(("2,5x^2", "x")), super::derivative_command("diff(2,5x^2)"));
+    assert_eq!(Some(("x^2", "y")), super::derivative_command("diff(x^2, y)"));
+    assert_eq!(Some(("ggT(x; 2)", "x")), super::derivative_command("diff(ggT(x; 2); x)"));
+    assert_eq!(Some(("3x", "y")), super::derivative_command("ableitung von 3x nach y"));
+    assert_eq!(None, super::derivative_command("diff(x^2"));
+}
+#[test]
+fn simplify_overflow() {
+    let settings = super::Settings::new();
+    // -2^63 hat keinen Betrag, der Teilausdruck bleibt unverändert stehen
+    assert_eq!("x ^ (0 - 9223372036854775807 - 1)", super::calculate_simplified("x^(0-9223372036854775807-1)", &settings).unwrap());
+    assert_eq!("(0 - 9223372036854775807 - 1) × x", super::calculate_simplified("(0-9223372036854775807-1) * x", &settings).unwrap());
+    assert!(super::calculate_derivative("x^(0-9223372036854775807-1)", "x", &settings).is_ok());
+}
+#[test]
+fn solve_1() {
+    let mut settings = super::Settings::new();
+    settings.implicit_multiplication = true;
+    settings.notation = super::settings::Notation::Fixed(8);
+    let solve = |input| super::calculate_solutions(input, &settings).unwrap();
+    assert_eq!("x = 5", solve("3x + 5 = 20"));
+    assert_eq!("x = 5 ÷ 3 ≈ 1.66666667", solve("3x = 5"));
+    assert_eq!("keine Lösung", solve("x + 1 = x"));
+    assert_eq!("unendlich viele Lösungen", solve("2x = x + x"));
+    // Ein Minus nach `=` ist ein Vorzeichen
+    assert_eq!("x = -3", solve("x = -3"));
+    assert_eq!("x = -3", solve("x = minus 3"));
+    assert_eq!("keine Lösung", solve("x^2 = -1"));
+    assert_eq!("x = -2\nx = 2", solve("x^2 = 4"));
+    assert_eq!("x = 1 - wurzel(2) ≈ -0.41421356\nx = 1 + wurzel(2) ≈ 2.41421356", solve("x^2 - 2x - 1 = 0"));
+    assert_eq!("x = -wurzel(3) ÷ 2 ≈ -0.86602540\nx = wurzel(3) ÷ 2 ≈ 0.86602540", solve("4x^2 = 3"));
+    assert_eq!("keine Lösung", solve("x^2 + 1 = 0"));
+    // Numerisch, die Polstelle von 1 ÷ x ist keine Lösung
+    assert_eq!("x ≈ 1.52137971", solve("x^3 - x - 2 = 0"));
+    assert_eq!("x ≈ 0.73908513", solve("cos(x) = x zwischen 0 und 1"));
+    assert_eq!("x ≈ 0.50000000", solve("1 / x = 2"));
+    assert_eq!("keine Lösung zwischen 0 und 1", solve("x^3 = 8 + x zwischen 0 und 1"));
+    match super::calculate_solutions("x + y = 2", &settings) {
+        Err(super::Error::TokenStream(_, errors)) => assert_eq!("Gleichung mit mehreren Unbekannten x, y nicht lösbar", errors[0].msg()),
+        _ => panic!("Expected unknowns error")
+    }
+}
+#[test]
+fn solve_2() {
+    let mut settings = super::Settings::new();
+    settings.implicit_multiplication = true;
+    settings.notation = super::settings::Notation::Fixed(8);
+    let solve = |input| super::calculate_solutions(input, &settings).unwrap();
+    // `0x` ist hier die Null mal x, keine hexadezimale Zahl
+    assert_eq!("unendlich viele Lösungen", solve("0x = 0"));
+    assert_eq!("x = 3", solve("0x + x = 3"));
+    assert_eq!("x = 2", solve("0x^2 + 2x = 4"));
+    assert_eq!("x = 31", solve("x = 0x1F"));
+    assert_eq!("x = -wurzel(2) ≈ -1.41421356\nx = wurzel(2) ≈ 1.41421356", solve("x^2 - 2 = 0"));
+    assert_eq!("x ≈ 3.14159265", solve("sin(x) = 0 zwischen 3 und 4"));
+    assert_eq!("y = -2", solve("2y + 4"));
+    // Die Ausgabe kann wieder eingegeben werden
+    let output = solve("2x + 6 = 0");
+    assert_eq!(output, super::calculate_solutions(output.as_str(), &settings).unwrap());
+    assert_eq!("x = -2\nx = 1", solve("x^2 = -x + 2"));
+    assert_eq!("wahr", calculate_output("2 ≠ -2", &settings).unwrap());
+    assert_eq!("falsch", calculate_output("1 < minus 3", &settings).unwrap());
+    match super::calculate_solutions("x < 2", &settings) {
+        Err(super::Error::TokenStream(_, errors)) => assert_eq!("Nur Gleichungen mit \"=\" lösbar", errors[0].msg()),
+        _ => panic!("Expected comparison error")
+    }
+}
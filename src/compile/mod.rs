@@ -0,0 +1,62 @@
+//! Ausdrücke mit Variablen welche einmal zerlegt und geprüft und danach
+//! beliebig oft ausgewertet werden, z.B.: für eine Wertetabelle oder einen
+//! Graphen.
+
+use super::error::{Error, ErrorStruct};
+use super::parse::ParseTree;
+use super::settings::Settings;
+use super::value::Value;
+
+/// Übersetzter Ausdruck, z.B.: `3x + 5` mit der Variablen `x`. Die Werte der
+/// Variablen werden bei [`CompiledExpr::evaluate`] in der Reihenfolge der
+/// Namen übergeben.
+pub struct CompiledExpr {
+    tree: ParseTree,
+    variables: Vec<&'static str>,
+    settings: Settings,
+}
+
+impl CompiledExpr {
+    /// Zerlegt und prüft `input` innerhalb der [`Limits`](crate::settings::Limits),
+    /// die Namen in `variables` sind klein geschrieben.
+    pub fn compile<'a>(input: &'a str, variables: &[&'static str], settings: &Settings) -> Result<Self, Error<'a>> {
+        let tree = super::parse_with(input, variables, settings)?;
+        Ok(Self {
+            tree,
+            variables: variables.to_vec(),
+            settings: settings.clone(),
+        })
+    }
+
+    /// Berechnet Teilausdrücke ohne Variablen schon jetzt, siehe [`ParseTree::fold`].
+    pub fn fold(self) -> Self {
+        Self {
+            tree: self.tree.fold(&self.settings),
+            ..self
+        }
+    }
+
+    pub fn variables(&self) -> &[&'static str] {
+        &self.variables
+    }
+
+    pub fn tree(&self) -> &ParseTree {
+        &self.tree
+    }
+
+    /// Wertet den Ausdruck mit `vars[idx]` als Wert der Variablen `idx` aus.
+    pub fn evaluate(&self, vars: &[f64]) -> Result<Value, ErrorStruct> {
+        if vars.len() != self.variables.len() {
+            let msg = format!("{} Werte für {} Variablen", vars.len(), self.variables.len());
+            return Err(ErrorStruct::new(self.tree.span(), msg))
+        }
+        self.tree.evaluate_with(&self.settings, vars)
+    }
+
+    /// Wie [`CompiledExpr::evaluate`], das Ergebnis muss eine Zahl sein.
+    pub fn evaluate_number(&self, vars: &[f64]) -> Result<f64, ErrorStruct> {
+        self.evaluate(vars)?
+            .number("Ergebnis")
+            .map_err(|msg| ErrorStruct::new(self.tree.span(), msg))
+    }
+}
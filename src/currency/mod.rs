@@ -0,0 +1,167 @@
+//! Währungen und Wechselkurse. Die Kurse werden aus einer lokalen Datei
+//! gelesen, es wird nie auf das Netzwerk zugegriffen.
+
+use std::fs;
+
+use super::decimal::Decimal;
+use super::settings::{Settings, Rounding};
+
+pub struct CurrencyDef {
+    pub code: &'static str,
+    /// Symbol der Währung für die Ausgabe, falls vorhanden.
+    pub symbol: Option<&'static str>,
+    /// Namen, Codes und Symbole in Kleinbuchstaben.
+    pub names: &'static [&'static str],
+    /// Übliche Anzahl an Stellen hinter dem Komma.
+    pub digits: u32,
+}
+
+pub static CURRENCIES: &[CurrencyDef] = &[
+    CurrencyDef { code: "EUR", symbol: Some("€"), names: &["eur", "euro", "€"], digits: 2 },
+    CurrencyDef { code: "USD", symbol: Some("$"), names: &["usd", "dollar", "$"], digits: 2 },
+    CurrencyDef { code: "GBP", symbol: Some("£"), names: &["gbp", "pfund", "£"], digits: 2 },
+    CurrencyDef { code: "JPY", symbol: Some("¥"), names: &["jpy", "yen", "¥"], digits: 0 },
+    CurrencyDef { code: "CHF", symbol: None, names: &["chf", "franken"], digits: 2 },
+    CurrencyDef { code: "CNY", symbol: None, names: &["cny", "yuan"], digits: 2 },
+    CurrencyDef { code: "SEK", symbol: None, names: &["sek"], digits: 2 },
+    CurrencyDef { code: "NOK", symbol: None, names: &["nok"], digits: 2 },
+    CurrencyDef { code: "DKK", symbol: None, names: &["dkk"], digits: 2 },
+    CurrencyDef { code: "PLN", symbol: None, names: &["pln", "zloty"], digits: 2 },
+    CurrencyDef { code: "CZK", symbol: None, names: &["czk"], digits: 2 },
+    CurrencyDef { code: "BHD", symbol: None, names: &["bhd"], digits: 3 },
+];
+
+/// Sucht eine Währung anhand von Name, Code oder Symbol.
+pub fn find(name: &str) -> Option<usize> {
+    CURRENCIES.iter().position(|currency| currency.names.contains(&name))
+}
+
+pub fn display(idx: usize) -> &'static str {
+    match CURRENCIES[idx].symbol {
+        Some(symbol) => symbol,
+        None => CURRENCIES[idx].code
+    }
+}
+
+/// Wechselkurse, zu jeder Währung der Wert einer Einheit in einer
+/// gemeinsamen Bezugswährung.
+#[derive(Debug, Clone)]
+pub struct RateTable {
+    rates: Vec<(usize, Decimal)>,
+}
+
+impl RateTable {
+    /// Liest eine Kurstabelle, pro Zeile eine Währung und ihr Kurs in der
+    /// Bezugswährung, diese wird mit `basis` angegeben, z.B.:
+    ///
+    /// ```text
+    /// # 1 Einheit in EUR
+    /// basis EUR
+    /// USD 0,92
+    /// ```
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut rates = Vec::new();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') { continue }
+
+            let mut words = line.split_whitespace();
+            let (code, rate) = match (words.next(), words.next(), words.next()) {
+                (Some("basis"), Some(code), None) => (code, "1"),
+                (Some(code), Some(rate), None) => (code, rate),
+                _ => return Err(format!("Zeile {}: Währung und Kurs erwartet", idx +1))
+            };
+            let currency = match find(code.to_lowercase().as_str()) {
+                Some(currency) => currency,
+                None => return Err(format!("Zeile {}: Unbekannte Währung {}", idx +1, code))
+            };
+            let rate = match Decimal::parse(rate) {
+                Some(rate) if !rate.is_zero() && rate.to_f64() > 0f64 => rate,
+                _ => return Err(format!("Zeile {}: Ungültiger Kurs {}", idx +1, rate))
+            };
+            rates.retain(|(c, _)| *c != currency);
+            rates.push((currency, rate));
+        }
+        Ok(Self { rates })
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(content.as_str()),
+            Err(e) => Err(format!("Kurstabelle {} nicht lesbar: {}", path, e))
+        }
+    }
+
+    fn rate(&self, currency: usize) -> Result<Decimal, String> {
+        match self.rates.iter().find(|(c, _)| *c == currency) {
+            Some((_, rate)) => Ok(*rate),
+            None => Err(format!("Kein Kurs für {} vorhanden", CURRENCIES[currency].code))
+        }
+    }
+
+    /// Rechnet einen Betrag von einer Währung in eine andere um.
+    pub fn convert(&self, amount: &Decimal, from: usize, to: usize) -> Result<Decimal, String> {
+        if from == to {
+            return Ok(*amount)
+        }
+        let from_rate = self.rate(from)?;
+        let to_rate = self.rate(to)?;
+        let res = amount.mul(&from_rate).and_then(|base| base.div(&to_rate));
+        match res {
+            Some(res) => Ok(res),
+            None => Err("Betrag zu groß".to_string())
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rates.len()
+    }
+}
+
+/// Anzahl der Stellen hinter dem Komma einer Währung, eine Einstellung in
+/// [`Settings::currency_digits`] hat Vorrang.
+pub fn digits(idx: usize, settings: &Settings) -> u32 {
+    match settings.currency_digits.iter().find(|(c, _)| *c == idx) {
+        Some((_, digits)) => *digits,
+        None => CURRENCIES[idx].digits
+    }
+}
+
+/// Wertet die Kommandos `rundung`, `stellen` und `kurse` aus, z.B.:
+/// `rundung bankier`, `stellen EUR 3` oder `kurse kurse.txt`.
+pub fn configure(settings: &mut Settings, input: &str) -> Option<Result<String, String>> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let res = match words.as_slice() {
+        ["rundung", "kaufmännisch"] => {
+            settings.rounding = Rounding::Commercial;
+            Ok("Kaufmännische Rundung, 0,005 wird zu 0,01".to_string())
+        }
+        ["rundung", "bankier"] => {
+            settings.rounding = Rounding::Bankers;
+            Ok("Mathematische Rundung, 0,005 wird zu 0,00 und 0,015 zu 0,02".to_string())
+        }
+        ["rundung", ..] => Err("Rundung \"kaufmännisch\" oder \"bankier\" erwartet".to_string()),
+        ["stellen", code, digits] => {
+            match (find(code.to_lowercase().as_str()), digits.parse::<u32>()) {
+                (Some(currency), Ok(digits)) if digits <= 8 => {
+                    settings.currency_digits.retain(|(c, _)| *c != currency);
+                    settings.currency_digits.push((currency, digits));
+                    Ok(format!("{} mit {} Stellen hinter dem Komma", CURRENCIES[currency].code, digits))
+                }
+                (None, _) => Err(format!("Unbekannte Währung {}", code)),
+                _ => Err(format!("Ungültige Anzahl an Stellen: {}", digits))
+            }
+        }
+        ["stellen", ..] => Err("Währung und Anzahl der Stellen erwartet, z.B.: \"stellen EUR 3\"".to_string()),
+        ["kurse", path] => {
+            RateTable::load(path).map(|rates| {
+                let msg = format!("{} Kurse geladen", rates.len());
+                settings.rates = Some(rates);
+                msg
+            })
+        }
+        ["kurse", ..] => Err("Pfad zur Kurstabelle erwartet, z.B.: \"kurse kurse.txt\"".to_string()),
+        _ => return None
+    };
+    Some(res)
+}
@@ -0,0 +1,91 @@
+//! Kalenderdaten im gregorianischen Kalender. Ein Datum wird als Anzahl der
+//! Tage seit dem 1.1.1970 gespeichert, somit ist die Differenz zweier Daten
+//! eine einfache Subtraktion.
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Date {
+    days: i64,
+}
+
+impl Date {
+    /// Erstellt ein Datum aus Tag, Monat und Jahr, ungültige Daten wie der
+    /// 30.2. ergeben [`None`].
+    pub fn from(day: u32, month: u32, year: i64) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(month, year) {
+            return None
+        }
+        // Days from civil, the year starts in march so the leap day is the last day
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = month as i64;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Some(Self { days: era * 146_097 + day_of_era - 719_468 })
+    }
+
+    /// Das heutige Datum in UTC.
+    pub fn today() -> Self {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs() as i64),
+        };
+        Self { days: seconds.div_euclid(86_400) }
+    }
+
+    /// Tag, Monat und Jahr.
+    pub fn civil(&self) -> (u32, u32, i64) {
+        let days = self.days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        (day, month, year)
+    }
+
+    pub fn add_days(&self, days: i64) -> Option<Self> {
+        let days = self.days.checked_add(days)?;
+        // Stay within years with four digits
+        if !(-719_162..=2_932_896).contains(&days) {
+            return None
+        }
+        Some(Self { days })
+    }
+
+    /// Anzahl der Tage von `other` bis zu diesem Datum.
+    pub fn days_since(&self, other: &Date) -> i64 {
+        self.days - other.days
+    }
+
+    pub fn weekday(&self) -> &'static str {
+        const WEEKDAYS: [&str; 7] = ["Donnerstag", "Freitag", "Samstag", "Sonntag", "Montag", "Dienstag", "Mittwoch"];
+        WEEKDAYS[self.days.rem_euclid(7) as usize]
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(month: u32, year: i64) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let (day, month, year) = self.civil();
+        write!(f, "{:02}.{:02}.{}", day, month, year)
+    }
+}
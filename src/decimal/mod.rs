@@ -0,0 +1,209 @@
+//! Exakte Dezimalzahlen für Geldbeträge. Eine Zahl besteht aus einer
+//! Ganzzahl und der Anzahl der Stellen hinter dem Komma, `12,50` ist
+//! `1250` mit zwei Stellen.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::settings::Rounding;
+
+/// Maximale Anzahl an Stellen hinter dem Komma, z.B.: bei `10 € / 3`.
+const MAX_SCALE: u32 = 18;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        Self {
+            mantissa,
+            scale,
+        }
+    }
+
+    /// Liest eine Dezimalzahl mit Komma oder Punkt ein, z.B.: `12,50`. Mehr
+    /// als [`MAX_SCALE`] Stellen hinter dem Komma werden gerundet.
+    pub fn parse(literal: &str) -> Option<Self> {
+        let (sign, literal) = match literal.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, literal)
+        };
+        let (integer, fraction) = match literal.find([',', '.']) {
+            Some(idx) => (&literal[..idx], &literal[idx +1..]),
+            None => (literal, "")
+        };
+        if integer.is_empty() || !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+            return None
+        }
+
+        let (fraction, rest) = fraction.split_at(fraction.len().min(MAX_SCALE as usize));
+        let mut mantissa: i128 = 0;
+        for c in integer.chars().chain(fraction.chars()) {
+            mantissa = mantissa.checked_mul(10)?.checked_add(c.to_digit(10)? as i128)?;
+        }
+        // Round half to even on the remaining digits, like `mul` and `div`
+        let round_up = match rest.as_bytes() {
+            [] => false,
+            [first, tail @ ..] => match first.cmp(&b'5') {
+                Ordering::Greater => true,
+                Ordering::Less => false,
+                Ordering::Equal => tail.iter().any(|c| *c != b'0') || mantissa % 2 != 0,
+            }
+        };
+        if round_up {
+            mantissa = mantissa.checked_add(1)?;
+        }
+        Some(Self::new(sign * mantissa, fraction.len() as u32).normalize())
+    }
+
+    /// Wandelt eine [`f64`] über ihre kürzeste Darstellung um, somit wird
+    /// aus `0.19` exakt `0,19`.
+    pub fn from_f64(n: f64) -> Option<Self> {
+        if !n.is_finite() {
+            return None
+        }
+        let literal = format!("{}", n);
+        Self::parse(literal.as_str())
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.to_string().parse().unwrap()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    /// Entfernt Nullen am Ende, `12,50` wird zu `12,5`.
+    fn normalize(mut self) -> Self {
+        while self.scale > 0 && self.mantissa % 10 == 0 {
+            self.mantissa /= 10;
+            self.scale -= 1;
+        }
+        self
+    }
+
+    fn rescale(&self, scale: u32) -> Option<Self> {
+        let factor = 10i128.checked_pow(scale - self.scale)?;
+        Some(Self::new(self.mantissa.checked_mul(factor)?, scale))
+    }
+
+    pub fn add(&self, other: &Decimal) -> Option<Self> {
+        let scale = self.scale.max(other.scale);
+        let left = self.rescale(scale)?;
+        let right = other.rescale(scale)?;
+        Some(Self::new(left.mantissa.checked_add(right.mantissa)?, scale).normalize())
+    }
+
+    pub fn sub(&self, other: &Decimal) -> Option<Self> {
+        self.add(&other.neg())
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(-self.mantissa, self.scale)
+    }
+
+    pub fn mul(&self, other: &Decimal) -> Option<Self> {
+        let res = Self::new(self.mantissa.checked_mul(other.mantissa)?, self.scale.checked_add(other.scale)?);
+        if res.scale > MAX_SCALE {
+            return Some(res.round(MAX_SCALE, Rounding::Bankers).normalize())
+        }
+        Some(res.normalize())
+    }
+
+    /// Division mit höchstens [`MAX_SCALE`] Stellen hinter dem Komma.
+    pub fn div(&self, other: &Decimal) -> Option<Self> {
+        if other.is_zero() {
+            return None
+        }
+        // self / other * 10^scale = self.mantissa * 10^(scale + other.scale - self.scale) / other.mantissa
+        let mut scale = MAX_SCALE;
+        loop {
+            let exponent = (scale + other.scale) as i64 - self.scale as i64;
+            let numerator = match exponent {
+                e if e >= 0 => 10i128.checked_pow(e as u32).and_then(|f| self.mantissa.checked_mul(f)),
+                // A divisor beyond i128 leaves nothing of the mantissa
+                e => Some(10i128.checked_pow((-e) as u32).map_or(0, |f| self.mantissa / f)),
+            };
+            match numerator {
+                Some(numerator) => {
+                    let quotient = div_round(numerator, other.mantissa, Rounding::Bankers);
+                    return Some(Self::new(quotient, scale).normalize())
+                }
+                None if scale > 0 => scale -= 1,
+                None => return None
+            }
+        }
+    }
+
+    /// Rundet auf `digits` Stellen hinter dem Komma.
+    pub fn round(&self, digits: u32, rounding: Rounding) -> Self {
+        if self.scale <= digits {
+            return *self
+        }
+        match 10i128.checked_pow(self.scale - digits) {
+            Some(divisor) => Self::new(div_round(self.mantissa, divisor, rounding), digits),
+            // The divisor is larger than any mantissa
+            None => Self::new(0, digits),
+        }
+    }
+
+    /// Formatiert mit genau `digits` Stellen hinter dem Komma, vorher muss
+    /// mit [`Decimal::round`] gerundet werden.
+    pub fn format(&self, digits: u32) -> String {
+        match self.rescale(digits.max(self.scale)) {
+            Some(d) => d.to_string(),
+            None => self.to_string()
+        }
+    }
+}
+
+/// Ganzzahlige Division mit Rundung des Rests.
+fn div_round(numerator: i128, divisor: i128, rounding: Rounding) -> i128 {
+    let quotient = numerator / divisor;
+    let remainder = numerator % divisor;
+    if remainder == 0 {
+        return quotient
+    }
+    let sign = if (numerator < 0) != (divisor < 0) { -1 } else { 1 };
+    let twice = (remainder.abs()).saturating_mul(2);
+    let round_up = match twice.cmp(&divisor.abs()) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Less => false,
+        std::cmp::Ordering::Equal => match rounding {
+            Rounding::Commercial => true,
+            Rounding::Bankers => quotient % 2 != 0,
+        }
+    };
+    if round_up { quotient + sign } else { quotient }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.sub(other) {
+            Some(difference) => Some(difference.mantissa.cmp(&0)),
+            None => self.to_f64().partial_cmp(&other.to_f64())
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits)
+        }
+        let digits = format!("{:0>width$}", digits, width = scale +1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
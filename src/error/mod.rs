@@ -2,38 +2,34 @@ use std::io;
 use std::fmt;
 use std::convert;
 
-use super::stream::InputStream;
-use super::token::InputToken;
 use super::types::StartEnd;
 
 #[derive(Debug)]
-pub enum Error {
+pub enum Error<'a> {
     Dummy,
     ReadInput(io::Error),
-    TokenStream(InputStream, Vec<ErrorStruct>),
+    /// Fehler in der Eingabe, die Stellen der [`ErrorStruct`] sind Bytes in
+    /// dieser Eingabe.
+    TokenStream(&'a str, Vec<ErrorStruct>),
     ParseTree(String),
     ParseNode(String),
+    Evaluate(String),
+    /// Eingabe länger als [`Limits::input`](crate::settings::Limits::input) Zeichen.
+    InputTooLong(usize),
+    /// Mehr Token als [`Limits::tokens`](crate::settings::Limits::tokens).
+    TooManyTokens(usize),
+    /// Baum tiefer als [`Limits::depth`](crate::settings::Limits::depth).
+    TooDeep(usize),
+    /// Mehr Rechenschritte als [`Limits::steps`](crate::settings::Limits::steps).
+    TooManySteps(usize),
 }
 
-impl fmt::Display for Error {
+impl fmt::Display for Error<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
             Error::Dummy => write!(f, "Dummy Error"),
             Error::ReadInput(e) => write!(f, "Fehler beim lesen der Eingabe: {}", e),
-            Error::TokenStream(input_stream, errors) => {
-                use InputToken::*;
-
-                let mut input = String::new();
-                for token in input_stream {
-                    match token {
-                        Space => input.push(' '),
-                        Letter(c) |
-                        Digit(c) |
-                        Symbol(c) |
-                        Whatever(c) => input.push(*c)
-                    }
-                }
-
+            Error::TokenStream(input, errors) => {
                 let mut msg = String::new();
                 msg.push_str("Fehler:");
                 msg.push('\n');
@@ -44,17 +40,20 @@ impl fmt::Display for Error {
                         msg.push('-');
                     }
                     msg.push(' ');
-                    msg.push_str(input.as_str());
+                    msg.push_str(input);
                     msg.push('\n');
                     msg.push('\r');
                         
                     for _ in 0..9 {
                         msg.push(' ');
                     }
-                    for _ in 0..err.range.start {
+                    // The caret is placed by characters, not by bytes
+                    let start = input.get(..err.range.start).map_or(0, |s| s.chars().count());
+                    let len = input.get(err.range.start..err.range.end).map_or(0, |s| s.chars().count());
+                    for _ in 0..start {
                         msg.push(' ');
                     }
-                    for _ in err.range.start..err.range.end {
+                    for _ in 0..len {
                         msg.push('^');
                     }
                     msg.push(' ');
@@ -69,11 +68,16 @@ impl fmt::Display for Error {
             }
             Error::ParseTree(msg) => write!(f, "ParseTree Error: {}", msg),
             Error::ParseNode(msg) => write!(f, "ParseNode Error: {}", msg),
+            Error::Evaluate(msg) => write!(f, "Fehler: {}", msg),
+            Error::InputTooLong(limit) => write!(f, "Fehler: Eingabe zu lang, höchstens {} Zeichen möglich", limit),
+            Error::TooManyTokens(limit) => write!(f, "Fehler: Eingabe zu lang, höchstens {} Token möglich", limit),
+            Error::TooDeep(limit) => write!(f, "Fehler: Ausdruck zu tief verschachtelt, höchstens {} Ebenen möglich", limit),
+            Error::TooManySteps(limit) => write!(f, "Fehler: Ausdruck zu groß, höchstens {} Rechenschritte möglich", limit),
         }
     }
 }
 
-impl convert::From<io::Error> for Error {
+impl convert::From<io::Error> for Error<'_> {
     fn from(e: io::Error) -> Self {
         Error::ReadInput(e)
     }
@@ -88,8 +92,16 @@ pub struct ErrorStruct {
 impl ErrorStruct {
     pub fn new(range: StartEnd, msg: String) -> Self {
         Self {
-            range: range,
+            range,
             msg,
         }
     }
+
+    pub fn range(&self) -> StartEnd {
+        self.range
+    }
+
+    pub fn msg(&self) -> &str {
+        self.msg.as_str()
+    }
 }
\ No newline at end of file
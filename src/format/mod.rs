@@ -0,0 +1,196 @@
+use super::settings::{Settings, Base, Notation, Grouping};
+use super::value::{Value, Money};
+use super::currency;
+
+/// Formatiert ein Ergebnis, Größen werden in ihrer Einheit ausgegeben.
+pub fn format_value(value: &Value, settings: &Settings) -> String {
+    match value {
+        Value::Number(n) => format_number(*n, settings),
+        Value::Quantity(q) => {
+            let n = format_decimal(q.display_value(), settings.notation, settings.grouping);
+            format!("{} {}", n, q.unit.symbol())
+        }
+        Value::Money(m) => format_money(m, settings),
+        Value::Date(d) => format!("{}, {}", d.weekday(), d),
+        Value::Bool(true) => "wahr".to_string(),
+        Value::Bool(false) => "falsch".to_string(),
+    }
+}
+
+/// Formatiert einen Geldbetrag mit den Stellen der Währung, z.B.: `16,49 €`.
+/// Ohne Gruppierung wird das Komma als Dezimaltrennzeichen verwendet.
+pub fn format_money(money: &Money, settings: &Settings) -> String {
+    let digits = currency::digits(money.currency, settings);
+    let amount = money.amount.round(digits, settings.rounding).format(digits);
+    let amount = match settings.grouping {
+        Grouping::None => amount.replace('.', ","),
+        grouping => group(amount.as_str(), grouping),
+    };
+    format!("{} {}", amount, currency::display(money.currency))
+}
+
+/// Formatiert ein Ergebnis entsprechend der [`Settings`]. Bei einem anderen
+/// Zahlensystem als [`Base::Decimal`] werden ganze Zahlen als 64 Bit
+/// Zweierkomplement ausgegeben, z.B.: `-1` als `0xFFFFFFFFFFFFFFFF`.
+pub fn format_number(n: f64, settings: &Settings) -> String {
+    let decimal = format_decimal(n, settings.notation, settings.grouping);
+    if settings.base == Base::Decimal || !n.is_finite() {
+        return decimal
+    }
+
+    if n.fract() != 0f64 || !n.is_finite() || n.abs() >= 9_223_372_036_854_775_808f64 {
+        return format!("{} (keine 64 Bit Ganzzahl)", decimal)
+    }
+    let n = n as i64;
+    match settings.base {
+        Base::Hexadecimal => format!("0x{:X}", n),
+        Base::Binary => format!("0b{:b}", n),
+        Base::Octal => format!("0o{:o}", n),
+        Base::Decimal => unreachable!()
+    }
+}
+
+/// Formatiert eine exakt berechnete ganze Zahl ohne Nachkommastellen, siehe
+/// [`Settings::exact`].
+pub fn format_integer(n: i64, settings: &Settings) -> String {
+    match settings.base {
+        Base::Decimal => group(n.to_string().as_str(), settings.grouping),
+        Base::Hexadecimal => format!("0x{:X}", n),
+        Base::Binary => format!("0b{:b}", n),
+        Base::Octal => format!("0o{:o}", n),
+    }
+}
+
+/// Formatiert eine Zahl im Dezimalsystem.
+pub fn format_decimal(n: f64, notation: Notation, grouping: Grouping) -> String {
+    if n.is_nan() {
+        return "nicht definiert".to_string()
+    }
+    if n.is_infinite() {
+        return match n.is_sign_negative() {
+            true => "-unendlich".to_string(),
+            false => "unendlich".to_string(),
+        }
+    }
+
+    let formatted = match notation {
+        Notation::Shortest => format!("{}", n),
+        Notation::Fixed(digits) => format!("{:.*}", digits, n),
+        Notation::Significant(digits) => significant(n, digits),
+        Notation::Scientific(digits) => format!("{:.*e}", digits, n),
+        Notation::Engineering(digits) => engineering(n, digits),
+    };
+
+    // `-0` und auf Null gerundete negative Zahlen ohne Vorzeichen
+    let formatted = match formatted.strip_prefix('-') {
+        Some(rest) if !rest.chars().any(|c| ('1'..='9').contains(&c)) => rest.to_string(),
+        _ => formatted
+    };
+
+    group(formatted.as_str(), grouping)
+}
+
+fn significant(n: f64, digits: usize) -> String {
+    let digits = digits.max(1);
+    if n == 0f64 {
+        return format!("{:.*}", digits -1, n)
+    }
+    // Round to the significant digits first, the exponent may change, e.g. 9.99 -> 10.0
+    let rounded: f64 = format!("{:.*e}", digits -1, n).parse().unwrap();
+    let exponent = rounded.abs().log10().floor() as i64;
+    let decimals = (digits as i64 - 1 - exponent).max(0) as usize;
+    format!("{:.*}", decimals, rounded)
+}
+
+fn engineering(n: f64, digits: usize) -> String {
+    if n == 0f64 {
+        return format!("{:.*}e0", digits, n)
+    }
+    let mut exponent = n.abs().log10().floor() as i32;
+    exponent -= exponent.rem_euclid(3);
+    let mut mantissa = n / 10f64.powi(exponent);
+    // Rounding may result in a mantissa of 1000, e.g. 999.9999 -> 1000.00
+    let rounded: f64 = format!("{:.*}", digits, mantissa).parse().unwrap();
+    if rounded.abs() >= 1000f64 {
+        exponent += 3;
+        mantissa = n / 10f64.powi(exponent);
+    }
+    format!("{:.*}e{}", digits, mantissa, exponent)
+}
+
+/// Gruppiert die Ziffern vor dem Komma und setzt das Dezimaltrennzeichen.
+fn group(formatted: &str, grouping: Grouping) -> String {
+    let (separator, decimal_point) = match grouping {
+        Grouping::None => return formatted.to_string(),
+        Grouping::German => ('.', ','),
+        Grouping::International => (',', '.'),
+    };
+
+    let (sign, rest) = match formatted.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", formatted)
+    };
+    let (mantissa, exponent) = match rest.find('e') {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, "")
+    };
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(idx) => (&mantissa[..idx], Some(&mantissa[idx +1..])),
+        None => (mantissa, None)
+    };
+
+    let mut res = String::from(sign);
+    for (idx, c) in integer.chars().enumerate() {
+        if idx > 0 && (integer.len() - idx) % 3 == 0 {
+            res.push(separator);
+        }
+        res.push(c);
+    }
+    if let Some(fraction) = fraction {
+        res.push(decimal_point);
+        res.push_str(fraction);
+    }
+    res.push_str(exponent);
+    res
+}
+
+/// Wertet ein `format` Kommando aus, z.B.: `genauigkeit 4` oder `gruppierung deutsch`.
+/// Gibt eine Beschreibung der neuen Einstellung zurück.
+pub fn configure(settings: &mut Settings, args: &str) -> Result<String, String> {
+    fn digits(word: &str) -> Result<usize, String> {
+        match word.parse::<usize>() {
+            Ok(n) if n <= 17 => Ok(n),
+            _ => Err(format!("Ungültige Anzahl an Stellen: {}", word))
+        }
+    }
+
+    let words: Vec<&str> = args.split_whitespace().collect();
+    match words.as_slice() {
+        ["kürzeste"] => settings.notation = Notation::Shortest,
+        ["genauigkeit", "0"] => return Err("Genauigkeit mindestens eine signifikante Stelle".to_string()),
+        ["genauigkeit", n] => settings.notation = Notation::Significant(digits(n)?),
+        ["nachkomma", n] => settings.notation = Notation::Fixed(digits(n)?),
+        ["wissenschaftlich"] => settings.notation = Notation::Scientific(6),
+        ["wissenschaftlich", n] => settings.notation = Notation::Scientific(digits(n)?),
+        ["technisch"] => settings.notation = Notation::Engineering(3),
+        ["technisch", n] => settings.notation = Notation::Engineering(digits(n)?),
+        ["gruppierung", "deutsch"] => settings.grouping = Grouping::German,
+        ["gruppierung", "international"] => settings.grouping = Grouping::International,
+        ["gruppierung", "keine"] => settings.grouping = Grouping::None,
+        _ => return Err(format!("Unbekanntes Format: {}", args))
+    }
+
+    let notation = match settings.notation {
+        Notation::Shortest => "kürzeste Darstellung".to_string(),
+        Notation::Significant(n) => format!("{} signifikante Stellen", n),
+        Notation::Fixed(n) => format!("{} Nachkommastellen", n),
+        Notation::Scientific(n) => format!("wissenschaftlich mit {} Nachkommastellen", n),
+        Notation::Engineering(n) => format!("technisch mit {} Nachkommastellen", n),
+    };
+    let grouping = match settings.grouping {
+        Grouping::None => "keine Gruppierung",
+        Grouping::German => "deutsche Gruppierung",
+        Grouping::International => "internationale Gruppierung",
+    };
+    Ok(format!("Format: {}, {}", notation, grouping))
+}
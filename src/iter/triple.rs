@@ -12,22 +12,19 @@ impl<'a, T> Iterator for TripleIter<'a, T>
     fn next(&mut self) -> Option<Self::Item> {
         if self.count >= self.len { return None }
 
-        let prev: Option<&'a T::Output>;
-        let next: Option<&'a T::Output>;
-
-        if self.count == 0 {
-            prev = None;
+        let prev = if self.count == 0 {
+            None
         } else {
-            prev = Some(&self.inner[self.count -1]);
-        }
+            Some(&self.inner[self.count -1])
+        };
 
         let current = Some(&self.inner[self.count]);
 
-        if self.count +1 < self.len {
-            next = Some(&self.inner[self.count +1]);
+        let next = if self.count +1 < self.len {
+            Some(&self.inner[self.count +1])
         } else {
-            next = None
-        }
+            None
+        };
         self.count += 1;
         Some((prev, current, next))
     }
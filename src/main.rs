@@ -19,10 +19,18 @@
 //! * `1` bis `0`
 //! * `+`
 //! * `-`
-//! * `*`
-//! * `/`
+//! * `*` und `×`
+//! * `/` und `÷`
 //! * `(`
 //! * `)`
+//! * `%` und `‰`
+//! * `!` und `;`
+//! * `^` für die Potenz, z.B.: `2^10`
+//! * `&`, `|`, `<<` und `>>`
+//! * `,` als Dezimalkomma, z.B.: `12,50`
+//! * `€`, `$`, `£` und `¥`
+//! * `.` in einem Datum, z.B.: `17.10.2026`
+//! * `<`, `>`, `=`, `≠`, `≤`, `≥` und `?`
 //! 
 //! Gültige Wörter:
 //! * `plus`
@@ -31,37 +39,172 @@
 //! * `durch`
 //! * `auf`
 //! * `zu`
+//! * `prozent`, `promille` und `von`
+//! * `mod`, `modulo`, `div` und `fakultät`
+//! * `ggT` und `kgV`, z.B.: `ggT(12; 18)`
+//! * `hoch` für die Potenz, z.B.: `zwei hoch zehn`
+//! * `sinus`, `kosinus`, `tangens`, `exp`, `ln` und `wurzel`, z.B.: `sinus von x` oder `wurzel(2)`
+//! * `xor` und `nicht`
+//! * Währungen wie `Euro`, `Dollar`, `EUR` oder `USD`
+//! * `heute` und `zwischen ... und ...`
+//! * `kleiner als`, `größer als`, `gleich`, `ungleich`, `und`, `oder` und `ist`
+//! * `wenn`, `dann` und `sonst`
 //! * und viele ausgeschriebenen Zahlen z.B.: `einhundert` oder 
-//! * `eintausenddreihundertfünf` usw.. 
+//! * `eintausenddreihundertfünf` usw..
+//!
+//! Mit `implizit an` werden zwei direkt aufeinander folgende Operanden
+//! multipliziert, z.B. `2(3+4)` oder `(1+2)(3+4)`. Die implizite Multiplikation
+//! bindet dabei stärker als `*` und `/`.
+//!
+//! Ganze Zahlen müssen in 64 Bit passen, sonst meldet die Eingabe `Zahl zu groß`.
+//! Mit `exakt an` werden Ausdrücke aus ganzen Zahlen exakt mit 64 Bit berechnet,
+//! z.B.: ergibt `9007199254740993 + 0` dann `9007199254740993` statt `9007199254740992`.
+//! Ein Überlauf ergibt ebenfalls den Fehler `Zahl zu groß`. Sobald eine Kommazahl,
+//! eine Einheit oder eine nicht aufgehende Division vorkommt, wird wie sonst gerechnet.
+//!
+//! Wird ein Ergebnis zu groß für eine Kommazahl, z.B.: bei sehr vielen Multiplikationen,
+//! ergibt dies einen Fehler an der Stelle an welcher der Wert entsteht. Mit `unendlich anzeigen`
+//! wird stattdessen `unendlich`, `-unendlich` oder `nicht definiert` ausgegeben, `unendlich fehler`
+//! schaltet zurück. Teilen durch Null bleibt immer ein Fehler, `-0` wird als `0` ausgegeben.
+//!
+//! Zahlen können auch hexadezimal, binär oder oktal eingegeben werden, z.B.:
+//! `0x1F`, `0b1010`, `0o17` oder `1F hex`. Mit `basis hex`, `basis bin`, `basis okt`
+//! und `basis dez` wird das Zahlensystem der Ausgabe gewechselt. Ohne folgende Ziffer ist
+//! `0x` die Null vor der Variablen `x`, z.B.: in `löse 0x + x = 3`.
+//!
+//! Das Ausgabeformat kann mit `format` eingestellt werden, z.B.: `format kürzeste`,
+//! `format genauigkeit 4`, `format nachkomma 2`, `format wissenschaftlich 3`,
+//! `format technisch 3` oder `format gruppierung deutsch` für `1.234.567,89`.
+//! Standard ist die kürzeste Darstellung, `1 + 1` ergibt `2`.
+//!
+//! Zahlen können eine Einheit haben, z.B.: `3 Meter plus 20 Zentimeter in Millimeter`
+//! oder `5 km / 2 h`. Mit `in` oder `nach` wird in eine andere Einheit umgerechnet.
+//! Größen mit unterschiedlicher Dimension können nicht addiert werden. 
+//!
+//! Geldbeträge werden exakt im Dezimalsystem berechnet, z.B.: `12,50 € plus 3,99 €`
+//! ergibt `16,49 €`. Die Ausgabe wird auf die Stellen der Währung gerundet, mit
+//! `rundung kaufmännisch` oder `rundung bankier` wird die Rundung gewählt und mit
+//! `stellen EUR 3` die Anzahl der Stellen. Wechselkurse werden mit `kurse kurse.txt`
+//! aus einer lokalen Datei geladen, pro Zeile eine Währung und ihr Kurs in der
+//! Bezugswährung, z.B.: `basis EUR` und `USD 0,92`.
+//! Danach rechnet z.B. `100 $ in €` in eine andere Währung um.
+//!
+//! Ein Datum wird als `17.10.2026` oder `heute` eingegeben und mit Zeitdauern
+//! verrechnet, z.B.: `17.10.2026 plus 3 Wochen` oder `Tage zwischen 1.1.2026 und 24.12.2026`.
+//! Das Ergebnis wird im deutschen Format mit Wochentag ausgegeben. Zeitdauern und
+//! andere Größen können zusammengesetzt werden, z.B.: `2 Stunden 30 Minuten mal 4`.
+//!
+//! Vergleiche ergeben `wahr` oder `falsch`, z.B.: `ist 3 mal 4 größer als 10?`.
+//! Mit `und`, `oder` und `nicht` werden Vergleiche verknüpft, dabei bindet `nicht`
+//! stärker als `und` und `und` stärker als `oder`. Ein alleinstehendes `und` ist
+//! das logische `und`, außer in `zwischen a und b`. In einem Zahlwort wie
+//! `dreiundzwanzig` bleibt es Teil der Zahl. Vor einer Zahl ist `nicht` weiterhin
+//! das bitweise `nicht`.
+//!
+//! Mit `wenn a dann b sonst c` wird abhängig von einer Bedingung gerechnet, z.B.:
+//! `wenn 3 größer 0 dann 3 sonst minus 3`. Es wird nur der gewählte Zweig berechnet,
+//! der `sonst` Zweig reicht bis zum Ende der umgebenden Klammer.
+//!
+//! Mit `schritte an` wird zusätzlich jeder Rechenschritt in der Reihenfolge der
+//! Auswertung ausgegeben, z.B.: `1 + (-3) × 6 ÷ 2 → 1 + (-18) ÷ 2 → 1 + (-9) → -8`.
+//! Mit `schritte wörter` werden die Schritte ausgeschrieben, z.B.: `eins plus auf
+//! minus neun zu → minus acht`, und mit `schritte aus` wieder abgeschaltet.
+//!
+//! Die Eingabe wird mit so wenig Klammern wie nötig wiederholt, z.B.: `1 + 2 × 3`.
+//! Mit `darstellung ascii` werden nur ASCII Zeichen wie `*` und `/` verwendet, mit
+//! `darstellung unicode` die Zeichen `×` und `÷` und mit `darstellung wörter`
+//! ausgeschriebene Wörter, Zahlen mit Komma und ab einer Million bleiben dabei Ziffern.
+//! Die Ausgabe kann jeweils wieder eingegeben werden.
+//!
+//! Mit `export latex` oder `export mathml` wird die Eingabe zusätzlich als LaTeX,
+//! z.B.: `\frac{1}{2} \cdot 3`, oder als MathML ausgegeben, `export aus` schaltet dies
+//! wieder ab. Auf der Komandozeile wählt `--format latex` bzw. `--format mathml` das
+//! Format, ein Ausdruck wird dann direkt berechnet, z.B.: `term-calc --format latex "1/2"`.
+//!
+//! Mit `baum 1 + 2 * 3` wird der Baum der Eingabe ausgegeben, jeder Knoten mit
+//! seinem Operator, der Stelle seines Teilausdrucks in der Eingabe und seinem Wert. `baum dot 1 + 2 * 3`
+//! gibt den Baum im DOT Format von Graphviz aus. Auf der Komandozeile entspricht dies
+//! `--dump-tree` bzw. `--dump-tree=dot`, z.B.: `term-calc --dump-tree=dot "1+2" | dot -Tpng`.
+//!
+//! Mit `tokens einhundertfünf plus 3` werden die Stufen der Zerlegung ausgegeben:
+//! die Zeichenklassen der Eingabe, die Teile jedes Wortes wie `Ein 0..3, Hundert 3..10, Number(5) 10..14`
+//! und die daraus entstandenen Token mit ihrer Stelle in der Eingabe.
+//!
+//! Fehler zeigen auf den ganzen Teilausdruck, z.B.: bei `1 + 2 / 0` auf `2 / 0`, und
+//! bei zusammengesetzten Zahlwörtern auf das falsche Teilwort, z.B.: `zig` in `dreizehnzig`.
+//!
+//! Für Eingaben aus fremden Quellen, z.B.: in einem Chat Bot, begrenzt `Settings::limits`
+//! die Länge der Eingabe (100000 Zeichen), die Anzahl der Token (50000), die Tiefe des Baums
+//! (200 Ebenen) und die Anzahl der Rechenschritte (100000). Jede Grenze hat einen eigenen
+//! Fehler, Klammern um den ganzen Ausdruck wie `auf auf 1 zu zu` zählen nicht als Ebene.
+//!
+//! Zerlegung und Aufbau des Baums laufen in linearer Zeit zur Länge der Eingabe, auch bei
+//! tausenden Summanden. Eine lange Summe zählt dabei nur zwei Ebenen und wird bei Auswertung
+//! und Ausgabe ohne Rekursion durchlaufen, erst Klammern wie in `1 + (1 + (1 + 1))` ergeben
+//! weitere Ebenen. `cargo bench` misst lange Summen und tiefe Verschachtelung.
 
 #![allow(dead_code)]
 #![allow(unused)]
 
+mod compile;
+mod currency;
+mod date;
+mod decimal;
 mod error;
+mod format;
 mod iter;
 mod parse;
+mod settings;
 mod stream;
 mod token;
 mod types;
+mod unit;
+mod value;
+mod words;
 
-use error::{Error};
-use parse::ParseTree;
-use stream::{WordTokenStream, ParseStream, InputStream};
-use token::{ParseToken, WordToken};
-use types::{Operator, StartEnd};
+use error::{Error, ErrorStruct};
+use parse::{ParseTree, Dump, Solutions};
+pub use settings::{Settings, Base, Style, Export, NonFinite};
+pub use compile::CompiledExpr;
+use stream::ParseStream;
+use token::ParseToken;
+use types::Variable;
+use value::Value;
 
 use std::io;
 use std::fmt;
 
 const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Variablen der symbolischen Befehle wie `vereinfache`.
+pub const VARIABLES: [&str; 3] = ["x", "y", "z"];
+
+/// Bereich der numerischen Lösung von `löse` ohne `zwischen`.
+pub const INTERVAL: (f64, f64) = (-100f64, 100f64);
+
 fn main() {
-    println!("");
-    println!("");
+    let mut settings = Settings::new();
+    match parse_args(&mut settings, std::env::args().skip(1)) {
+        Ok(Args { expression: Some(expression), dump }) => {
+            match dump {
+                Some(format) => print_tree(expression.as_str(), &settings, format),
+                None => print_output(expression.as_str(), &settings),
+            }
+            return
+        }
+        Ok(_) => {}
+        Err(msg) => {
+            println!("Fehler: {}", msg);
+            return
+        }
+    }
+
+    println!();
+    println!();
     println!("Einfacher Komandozeilenrechner {} bereit!", PKG_VERSION);
     println!("Um das Programm zu beenden \"end\" eingeben.");
     println!("Um die Hilfe anzuzeigen \"help\" eingeben");
-    println!("");
+    println!();
     
     let stdin = io::stdin();
     let mut handle = stdin.lock();
@@ -81,28 +224,207 @@ fn main() {
                 print_help();
                 continue
             }
+            "implizit an" => {
+                settings.implicit_multiplication = true;
+                println!("Implizite Multiplikation eingeschaltet");
+                println!();
+                continue
+            }
+            "implizit aus" => {
+                settings.implicit_multiplication = false;
+                println!("Implizite Multiplikation ausgeschaltet");
+                println!();
+                continue
+            }
+            "exakt an" => {
+                settings.exact = true;
+                println!("Exakte Berechnung ganzer Zahlen eingeschaltet");
+                println!();
+                continue
+            }
+            "exakt aus" => {
+                settings.exact = false;
+                println!("Exakte Berechnung ganzer Zahlen ausgeschaltet");
+                println!();
+                continue
+            }
+            "unendlich fehler" => {
+                settings.non_finite = NonFinite::Error;
+                println!("Unendliche und nicht definierte Ergebnisse ergeben einen Fehler");
+                println!();
+                continue
+            }
+            "unendlich anzeigen" => {
+                settings.non_finite = NonFinite::Display;
+                println!("Unendliche und nicht definierte Ergebnisse werden angezeigt");
+                println!();
+                continue
+            }
+            "basis dez" |
+            "basis hex" |
+            "basis bin" |
+            "basis okt" => {
+                settings.base = match input.as_str() {
+                    "basis hex" => Base::Hexadecimal,
+                    "basis bin" => Base::Binary,
+                    "basis okt" => Base::Octal,
+                    _ => Base::Decimal,
+                };
+                println!("Ausgabe erfolgt im Zahlensystem {}", &input[6..]);
+                println!();
+                continue
+            }
+            "schritte an" |
+            "schritte wörter" |
+            "schritte aus" => {
+                settings.steps = match input.as_str() {
+                    "schritte an" => Some(settings.style),
+                    "schritte wörter" => Some(Style::Words),
+                    _ => None,
+                };
+                match settings.steps {
+                    Some(_) => println!("Rechenschritte werden ausgegeben"),
+                    None => println!("Rechenschritte werden nicht ausgegeben"),
+                }
+                println!();
+                continue
+            }
+            "darstellung ascii" |
+            "darstellung unicode" |
+            "darstellung wörter" => {
+                settings.style = match input.as_str() {
+                    "darstellung ascii" => Style::Ascii,
+                    "darstellung wörter" => Style::Words,
+                    _ => Style::Unicode,
+                };
+                println!("Eingabe wird in der Darstellung {} wiederholt", &input[12..]);
+                println!();
+                continue
+            }
+            "export latex" |
+            "export mathml" |
+            "export aus" => {
+                settings.export = match input.as_str() {
+                    "export latex" => Some(Export::Latex),
+                    "export mathml" => Some(Export::MathMl),
+                    _ => None,
+                };
+                match settings.export {
+                    Some(_) => println!("Eingabe wird zusätzlich als {} ausgegeben", &input[7..]),
+                    None => println!("Eingabe wird nicht mehr exportiert"),
+                }
+                println!();
+                continue
+            }
             "Was ist die Antwort auf alles?" => {
-                let res = calculate("42");
-                print_output(res);
+                print_output("42", &settings);
                 continue
             }
             _ => {}
         }
+
+        if let Some(expression) = input.strip_prefix("tokens ") {
+            println!("{}", stream::dump_tokens(expression, &settings));
+            continue
+        }
+
+        if let Some(expression) = input.strip_prefix("baum ") {
+            match expression.strip_prefix("dot ") {
+                Some(expression) => print_tree(expression, &settings, Dump::Dot),
+                None => print_tree(expression, &settings, Dump::Ascii),
+            }
+            continue
+        }
+
+        if let Some(expression) = input.strip_prefix("vereinfache ") {
+            // `2x` is the usual notation for symbolic input
+            let mut settings = settings.clone();
+            settings.implicit_multiplication = true;
+            match calculate_simplified(expression, &settings) {
+                Ok(res) => println!("{}", res),
+                Err(e) => println!("{}", e)
+            }
+            println!();
+            continue
+        }
+
+        if let Some((expression, variable)) = derivative_command(input.as_str()) {
+            let mut settings = settings.clone();
+            settings.implicit_multiplication = true;
+            match calculate_derivative(expression, variable, &settings) {
+                Ok(res) => println!("{}", res),
+                Err(e) => println!("{}", e)
+            }
+            println!();
+            continue
+        }
+
+        if let Some(equation) = input.strip_prefix("löse ") {
+            let mut settings = settings.clone();
+            settings.implicit_multiplication = true;
+            match calculate_solutions(equation, &settings) {
+                Ok(res) => println!("{}", res),
+                Err(e) => println!("{}", e)
+            }
+            println!();
+            continue
+        }
+
+        if let Some(args) = input.strip_prefix("format ") {
+            match format::configure(&mut settings, args) {
+                Ok(msg) => println!("{}", msg),
+                Err(msg) => println!("Fehler: {}", msg)
+            }
+            println!();
+            continue
+        }
+
+        if let Some(res) = currency::configure(&mut settings, input.as_str()) {
+            match res {
+                Ok(msg) => println!("{}", msg),
+                Err(msg) => println!("Fehler: {}", msg)
+            }
+            println!();
+            continue
+        }
         
-        let res =  calculate(input.as_str());
-        print_output(res);
+        print_output(input.as_str(), &settings);
     }
 }
 
-fn print_output(res: Result<(f64, String), Error>) {
+fn print_output(input: &str, settings: &Settings) {
+    let res = parse(input, settings).and_then(|tree| {
+        match evaluate(&tree, settings) {
+            Ok(output) => Ok((output, tree)),
+            Err(e) => Err(Error::TokenStream(input, vec![e]))
+        }
+    });
     match res {
-        Ok((output, input)) => {
-            println!("Eingabe: {}", input);
-            println!("Ausgabe: {:.8}", output);
+        Ok((output, tree)) => {
+            println!("Eingabe: {}", tree.pretty(settings.style));
+            match settings.export {
+                Some(Export::Latex) => println!("LaTeX: {}", tree.export(Export::Latex)),
+                Some(Export::MathMl) => println!("MathML: {}", tree.export(Export::MathMl)),
+                None => {}
+            }
+            if let Some(style) = settings.steps {
+                if let Ok(steps) = tree.steps(settings, style) {
+                    println!("Schritte: {}", steps.join(" \u{2192} "));
+                }
+            }
+            println!("Ausgabe: {}", output);
         }
         Err(e) => println!("{}", e)
     }
-    println!("");
+    println!();
+}
+
+fn print_tree(input: &str, settings: &Settings, format: Dump) {
+    match calculate_tree(input, settings, format) {
+        Ok(tree) => print!("{}", tree),
+        Err(e) => println!("{}", e)
+    }
+    println!();
 }
 
 fn print_help() {
@@ -112,22 +434,183 @@ fn print_help() {
     println!("  Subtraktion");
     println!("  Maltiplikation");
     println!("  Division");
-    println!("");
+    println!();
     println!("Die Operatoren können auch ausgeschrieben werden:");
     println!("  \"plus\" für Addition");
     println!("  \"minus\" für Subtraktion");
     println!("  \"mal\" für Multiplikation");
     println!("  \"durch\" für Division");
-    println!("");
+    println!();
     println!("Die Zahlen können auch ausgeschrieben werden:");
     println!("  z.B.: \"einhundertfünf\" für 105");
-    println!("");
+    println!();
+    println!("Prozentrechnung:");
+    println!("  \"%\" oder \"Prozent\" für Hundertstel, \"‰\" oder \"Promille\" für Tausendstel");
+    println!("  \"a + b%\" für a×(1+b/100), z.B.: \"250 plus 19 Prozent\" ergibt 297,5");
+    println!("  \"a - b%\" für a×(1-b/100), z.B.: \"80 - 25%\" ergibt 60");
+    println!("  \"b% von a\" für a×b/100, z.B.: \"19 % von 250\" ergibt 47,5");
+    println!();
+    println!("Ganzzahlige Rechenoperationen:");
+    println!("  \"mod\", \"modulo\" oder \"%\" für den Rest, z.B.: \"7 mod 3\" ergibt 1");
+    println!("  \"div\" für die ganzzahlige Division, z.B.: \"7 div 3\" ergibt 2");
+    println!("  \"!\" oder \"Fakultät\" für die Fakultät, z.B.: \"5!\" ergibt 120");
+    println!("  \"ggT(a; b)\" und \"kgV(a; b)\" für größten gemeinsamen Teiler");
+    println!("  und kleinstes gemeinsames Vielfaches, z.B.: \"ggT(12; 18)\" ergibt 6");
+    println!();
+    println!("Programmierer-Modus:");
+    println!("  Zahlen in anderen Zahlensystemen: \"0x1F\", \"0b1010\", \"0o17\" oder \"1F hex\"");
+    println!("  Bitoperationen auf 64 Bit Ganzzahlen: \"&\", \"|\", \"xor\", \"<<\", \">>\" und \"nicht\"");
+    println!("  \"basis hex\", \"basis bin\", \"basis okt\" oder \"basis dez\" eingeben");
+    println!("  um das Zahlensystem der Ausgabe zu wechseln");
+    println!();
+    println!("Ausgabeformat:");
+    println!("  \"format kürzeste\" für die kürzeste exakte Darstellung (Standard)");
+    println!("  \"format genauigkeit 4\" für 4 signifikante Stellen");
+    println!("  \"format nachkomma 2\" für 2 Stellen hinter dem Komma");
+    println!("  \"format wissenschaftlich 3\" für z.B.: 1.235e3");
+    println!("  \"format technisch 3\" für z.B.: 12.346e3");
+    println!("  \"format gruppierung deutsch\" für z.B.: 1.234.567,89");
+    println!("  \"format gruppierung international\" für z.B.: 1,234,567.89");
+    println!("  \"format gruppierung keine\" für z.B.: 1234567.89");
+    println!();
+    println!("Einheiten:");
+    println!("  Zahlen können eine Einheit haben, z.B.: \"3 Meter\", \"20 cm\" oder \"5 km / 2 h\"");
+    println!("  \"in\" oder \"nach\" rechnet in eine andere Einheit um,");
+    println!("  z.B.: \"3 Meter plus 20 Zentimeter in Millimeter\" ergibt 3200 mm");
+    println!("  Verfügbare Einheiten:");
+    for unit in unit::UNITS {
+        println!("    {}: {}", unit.symbol, unit.names.join(", "));
+    }
+    println!();
+    println!("Vergleiche und Logik:");
+    println!("  \"<\", \">\", \"=\", \"≠\", \"≤\" und \"≥\" oder \"kleiner als\", \"größer als\", \"gleich\"");
+    println!("  und \"ungleich\" vergleichen zwei Werte, das Ergebnis ist wahr oder falsch");
+    println!("  z.B.: \"ist 3 mal 4 größer als 10?\" ergibt wahr");
+    println!("  \"und\", \"oder\" und \"nicht\" verknüpfen Vergleiche, \"und\" bindet stärker als \"oder\"");
+    println!("  \"nicht\" vor einem Vergleich gilt für den ganzen Vergleich, vor einer Zahl bitweise");
+    println!("  \"wenn a dann b sonst c\" ergibt b falls a wahr ist und sonst c,");
+    println!("  z.B.: \"wenn 3 größer 0 dann 3 sonst minus 3\", berechnet wird nur b oder c");
+    println!();
+    println!("Datum und Zeitdauer:");
+    println!("  Ein Datum wird als \"17.10.2026\" oder \"heute\" eingegeben");
+    println!("  z.B.: \"17.10.2026 plus 3 Wochen\" ergibt Samstag, 07.11.2026");
+    println!("  \"Tage zwischen 1.1.2026 und 24.12.2026\" ergibt 357 d");
+    println!("  Zeitdauern können zusammengesetzt werden, z.B.: \"2 Stunden 30 Minuten mal 4\"");
+    println!();
+    println!("Währungen:");
+    println!("  Geldbeträge werden exakt berechnet, z.B.: \"12,50 € plus 3,99 €\" ergibt 16,49 €");
+    println!("  \"rundung kaufmännisch\" oder \"rundung bankier\" wählt die Rundung der Ausgabe");
+    println!("  \"stellen EUR 3\" gibt Euro mit 3 Stellen hinter dem Komma aus");
+    println!("  \"kurse kurse.txt\" lädt Wechselkurse aus einer Datei, pro Zeile eine Währung");
+    println!("  und ihr Kurs, z.B.: \"basis EUR\" und \"USD 0,92\" für 1 $ = 0,92 €");
+    println!("  danach rechnet z.B.: \"100 $ in €\" in eine andere Währung um");
+    println!("  Verfügbare Währungen:");
+    for currency in currency::CURRENCIES {
+        println!("    {}: {}", currency.code, currency.names.join(", "));
+    }
+    println!();
+    println!("Rechenschritte:");
+    println!("  \"schritte an\", \"schritte wörter\" oder \"schritte aus\" eingeben");
+    println!("  z.B.: \"1 + (-3) × 6 ÷ 2 → 1 + (-18) ÷ 2 → 1 + (-9) → -8\"");
+    println!("  oder ausgeschrieben \"eins plus auf minus neun zu → minus acht\"");
+    println!();
+    println!("Darstellung der Eingabe:");
+    println!("  \"darstellung ascii\" für z.B.: \"(1 + 2) * 3 / 4\"");
+    println!("  \"darstellung unicode\" für z.B.: \"(1 + 2) × 3 ÷ 4\"");
+    println!("  \"darstellung wörter\" für z.B.: \"auf eins plus zwei zu mal drei durch vier\"");
+    println!();
+    println!("Export:");
+    println!("  \"export latex\" gibt die Eingabe zusätzlich als LaTeX aus, z.B.: \"\\frac{{1}}{{2}} \\cdot 3\"");
+    println!("  \"export mathml\" gibt die Eingabe zusätzlich als MathML aus");
+    println!("  \"export aus\" schaltet den Export ab");
+    println!("  Auf der Komandozeile: \"term-calc --format latex 1/2\"");
+    println!();
+    println!("Token:");
+    println!("  \"tokens 1 + zweiundzwanzig\" zeigt die Zeichenklassen, die Teile jedes Wortes");
+    println!("  und die daraus entstandenen Token mit ihrer Stelle in der Eingabe");
+    println!();
+    println!("Baum:");
+    println!("  \"baum 1 + 2 * 3\" zeigt den Baum der Eingabe mit Stelle und Wert jedes Knotens");
+    println!("  \"baum dot 1 + 2 * 3\" gibt den Baum im DOT Format von Graphviz aus");
+    println!("  Auf der Komandozeile: \"term-calc --dump-tree 1+2\" oder \"--dump-tree=dot\"");
+    println!();
+    println!("Vereinfachen:");
+    println!("  \"vereinfache 2x + 3x - x * 1\" ergibt 4 × x, erlaubt sind die Variablen x, y und z");
+    println!("  Gleichartige Summanden werden zusammengefasst und Brüche gekürzt,");
+    println!("  z.B.: \"vereinfache (6x + 4) / 2\" ergibt 3 × x + 2");
+    println!();
+    println!("Ableiten:");
+    println!("  \"ableitung von x hoch 2 mal sinus von x nach x\" oder \"diff(x^2 sin(x); x)\"");
+    println!("  ergibt x ^ 2 × cos(x) + 2 × x × sin(x), ohne \"nach\" wird nach x abgeleitet");
+    println!();
+    println!("Gleichungen:");
+    println!("  \"löse 3x + 5 = 20\" ergibt x = 5, lineare Gleichungen werden exakt gelöst,");
+    println!("  quadratische mit der Lösungsformel, z.B.: \"löse x^2 - 2x - 1 = 0\"");
+    println!("  Alle anderen werden numerisch zwischen -100 und 100 gelöst, mit");
+    println!("  \"löse cos(x) = x zwischen 0 und 1\" in einem anderen Bereich");
+    println!();
+    println!("Implizite Multiplikation:");
+    println!("  \"implizit an\" oder \"implizit aus\" eingeben");
+    println!("  z.B.: \"2(3+4)\" oder \"(1+2)(3+4)\" für 2×(3+4) und (1+2)×(3+4)");
+    println!("  Die implizite Multiplikation bindet stärker als \"*\" und \"/\"");
+    println!();
+    println!("Exakte Berechnung:");
+    println!("  \"exakt an\" oder \"exakt aus\" eingeben");
+    println!("  Ausdrücke aus ganzen Zahlen werden exakt mit 64 Bit berechnet,");
+    println!("  z.B.: \"9007199254740993 + 0\" ergibt 9007199254740993 statt 9007199254740992");
+    println!("  Ein Überlauf ergibt den Fehler \"Zahl zu groß\"");
+    println!();
+    println!("Unendlich und nicht definiert:");
+    println!("  Wird ein Ergebnis zu groß für eine Kommazahl, ergibt dies einen Fehler");
+    println!("  \"unendlich anzeigen\" gibt stattdessen \"unendlich\" bzw. \"nicht definiert\" aus");
+    println!("  \"unendlich fehler\" schaltet wieder auf Fehler um, Teilen durch Null bleibt ein Fehler");
+    println!();
     println!("Du kannst auch nach der Antwort auf alles fragen :-)");
-    println!("");
-    println!("");
+    println!();
+    println!();
 }
 
-fn read_input(input: &mut impl io::BufRead) -> Result<String, Error> {
+/// Argumente der Komandozeile welche nicht in die [`Settings`] gehören.
+struct Args {
+    /// Ausdruck welcher direkt berechnet wird, ohne startet der Rechner.
+    expression: Option<String>,
+    /// Mit `--dump-tree` wird statt dem Ergebnis der Baum ausgegeben.
+    dump: Option<Dump>,
+}
+
+/// Liest die Argumente der Komandozeile, z.B.: `--format latex "1/2"`.
+fn parse_args(settings: &mut Settings, mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut expression = Vec::new();
+    let mut dump = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dump-tree" |
+            "--dump-tree=ascii" => dump = Some(Dump::Ascii),
+            "--dump-tree=dot" => dump = Some(Dump::Dot),
+            "--format" => {
+                settings.export = match args.next().as_deref() {
+                    Some("latex") => Some(Export::Latex),
+                    Some("mathml") => Some(Export::MathMl),
+                    Some("text") => None,
+                    Some(format) => return Err(format!("Unbekanntes Format \"{}\", erwartet latex, mathml oder text", format)),
+                    None => return Err("--format erwartet latex, mathml oder text".to_string())
+                };
+            }
+            arg if arg.starts_with("--") => return Err(format!("Unbekannte Option \"{}\"", arg)),
+            _ => expression.push(arg),
+        }
+    }
+    let expression = match expression.is_empty() {
+        true => None,
+        false => Some(expression.join(" "))
+    };
+    Ok(Args {
+        expression,
+        dump,
+    })
+}
+
+fn read_input(input: &mut impl io::BufRead) -> Result<String, Error<'static>> {
     use io::Write;
     
     // Print prompt
@@ -145,16 +628,193 @@ fn read_input(input: &mut impl io::BufRead) -> Result<String, Error> {
     Ok(buffer)
 }
 
-fn calculate(input: &str) -> Result<(f64, String), Error> {
-    let input_stream = InputStream::from(input);
-    let parse_stream = ParseStream::from(&input_stream)?;
-    parse_stream.validate()?;
-    let tree = ParseTree::from(&parse_stream[..])?;
-    let res = tree.evaluate();
+pub fn calculate(input: &str) -> Result<(f64, String), Error<'_>> {
+    calculate_with(input, &Settings::new())
+}
+
+pub fn calculate_with<'a>(input: &'a str, settings: &Settings) -> Result<(f64, String), Error<'a>> {
+    let (value, input) = calculate_value(input, settings)?;
+    match value {
+        Value::Number(n) => Ok((n, input)),
+        Value::Quantity(q) => Err(Error::Evaluate(format!("Ergebnis hat die Einheit {}", q.unit.symbol()))),
+        Value::Money(m) => Err(Error::Evaluate(format!("Ergebnis hat die Währung {}", currency::CURRENCIES[m.currency].code))),
+        Value::Date(_) => Err(Error::Evaluate("Ergebnis ist ein Datum".to_string())),
+        Value::Bool(_) => Err(Error::Evaluate("Ergebnis ist ein Wahrheitswert".to_string()))
+    }
+}
+
+pub fn calculate_value<'a>(input: &'a str, settings: &Settings) -> Result<(Value, String), Error<'a>> {
+    let tree = parse(input, settings)?;
+    let res = tree.evaluate(settings);
     match res {
-        Ok(n) => Ok((n, tree.print())),
-        Err(e) => unimplemented!()
+        Ok(value) => Ok((value, tree.print())),
+        Err(e) => Err(Error::TokenStream(input, vec![e]))
+    }
+}
+
+/// Ausgabe des Ergebnisses wie in der Eingabeaufforderung.
+pub fn calculate_output<'a>(input: &'a str, settings: &Settings) -> Result<String, Error<'a>> {
+    let tree = parse(input, settings)?;
+    evaluate(&tree, settings).map_err(|e| Error::TokenStream(input, vec![e]))
+}
+
+/// Berechnet das Ergebnis, mit `exakt an` für ganze Zahlen ohne Umweg über [`f64`].
+fn evaluate(tree: &ParseTree, settings: &Settings) -> Result<String, ErrorStruct> {
+    if settings.exact {
+        if let Some(n) = tree.evaluate_exact()? {
+            return Ok(format::format_integer(n, settings))
+        }
+    }
+    tree.evaluate(settings).map(|value| format::format_value(&value, settings))
+}
+
+/// Rechenschritte in der Reihenfolge der Auswertung, z.B.:
+/// `1 + (-18) ÷ 2`, `1 + (-9)` und `-8`.
+pub fn calculate_steps<'a>(input: &'a str, settings: &Settings, style: Style) -> Result<Vec<String>, Error<'a>> {
+    let tree = parse(input, settings)?;
+    tree.steps(settings, style).map_err(|e| Error::TokenStream(input, vec![e]))
+}
+
+/// Eingabe mit so wenig Klammern wie nötig in der Darstellung der [`Settings`].
+pub fn calculate_pretty<'a>(input: &'a str, settings: &Settings) -> Result<String, Error<'a>> {
+    let tree = parse(input, settings)?;
+    Ok(tree.pretty(settings.style))
+}
+
+/// Eingabe als LaTeX oder MathML.
+pub fn calculate_export<'a>(input: &'a str, settings: &Settings, format: Export) -> Result<String, Error<'a>> {
+    let tree = parse(input, settings)?;
+    Ok(tree.export(format))
+}
+
+/// Vereinfachte Eingabe mit den Variablen aus [`VARIABLES`], z.B.: `5 × x`
+/// für `2x + 3x`.
+pub fn calculate_simplified<'a>(input: &'a str, settings: &Settings) -> Result<String, Error<'a>> {
+    let tree = parse_with(input, &VARIABLES, settings)?;
+    Ok(tree.simplify(settings).pretty(settings.style))
+}
+
+/// Ableitung der Eingabe nach `variable` aus [`VARIABLES`], vereinfacht wie
+/// bei [`calculate_simplified`], z.B.: `2 × x` für `x^2` nach `x`.
+pub fn calculate_derivative<'a>(input: &'a str, variable: &str, settings: &Settings) -> Result<String, Error<'a>> {
+    let variable = match VARIABLES.iter().position(|name| name.eq_ignore_ascii_case(variable)) {
+        Some(idx) => Variable { name: VARIABLES[idx], idx },
+        None => return Err(Error::Evaluate(format!("Ableitung nach \"{}\" nicht möglich, erlaubt sind x, y und z", variable)))
+    };
+    let tree = parse_with(input, &VARIABLES, settings)?;
+    match tree.derive(variable, settings) {
+        Ok(tree) => Ok(tree.pretty(settings.style)),
+        Err(e) => Err(Error::TokenStream(input, vec![e]))
+    }
+}
+
+/// Ausdruck und Variable aus `ableitung von x^2 nach x` oder `diff(x^2; x)`,
+/// ohne Variable wird nach `x` abgeleitet.
+fn derivative_command(input: &str) -> Option<(&str, &str)> {
+    if let Some(rest) = input.strip_prefix("ableitung von ") {
+        return match rest.rsplit_once(" nach ") {
+            Some((expression, variable)) => Some((expression, variable.trim())),
+            None => Some((rest, VARIABLES[0]))
+        }
+    }
+    let args = input.strip_prefix("diff(")?.strip_suffix(')')?;
+    // The last separator outside of parentheses, a comma before a digit belongs to a number like `2,5`
+    let mut depth = 0;
+    let mut separator = None;
+    for (idx, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ';' if depth == 0 => separator = Some(idx),
+            ',' if depth == 0 && !args[idx +1..].starts_with(|c: char| c.is_ascii_digit()) => separator = Some(idx),
+            _ => {}
+        }
+    }
+    match separator {
+        Some(idx) => Some((&args[..idx], args[idx +1..].trim())),
+        None => Some((args, VARIABLES[0]))
+    }
+}
+
+/// Lösungen einer Gleichung mit einer Unbekannten aus [`VARIABLES`], eine
+/// pro Zeile, z.B.: `x = 5` für `3x + 5 = 20`. Mit `zwischen a und b` am
+/// Ende wird numerisch in diesem Bereich gesucht, sonst in [`INTERVAL`].
+pub fn calculate_solutions<'a>(input: &'a str, settings: &Settings) -> Result<String, Error<'a>> {
+    let (equation, interval) = match input.rsplit_once(" zwischen ") {
+        Some((equation, bounds)) => (equation, read_interval(bounds, settings)?),
+        None => (input, INTERVAL)
+    };
+    let tree = parse_with(equation, &VARIABLES, settings)?;
+    let solutions = match tree.solve(interval, settings) {
+        Ok(solutions) => solutions,
+        Err(e) => return Err(Error::TokenStream(equation, vec![e]))
+    };
+    let lines: Vec<String> = match solutions {
+        Solutions::None => vec!["keine Lösung".to_string()],
+        Solutions::All => vec!["unendlich viele Lösungen".to_string()],
+        Solutions::Exact(variable, roots) => roots.iter().map(|(root, value)| {
+            match value.fract() == 0f64 {
+                true => format!("{} = {}", variable.name, root.pretty(settings.style)),
+                false => format!("{} = {} ≈ {}", variable.name, root.pretty(settings.style), format::format_number(*value, settings)),
+            }
+        }).collect(),
+        Solutions::Numeric(_, roots) if roots.is_empty() => {
+            let mut settings = settings.clone();
+            settings.notation = settings::Notation::Shortest;
+            let (start, end) = interval;
+            vec![format!("keine Lösung zwischen {} und {}", format::format_number(start, &settings), format::format_number(end, &settings))]
+        }
+        Solutions::Numeric(variable, roots) => roots.iter().map(|root| format!("{} ≈ {}", variable.name, format::format_number(*root, settings))).collect(),
+    };
+    Ok(lines.join("\n"))
+}
+
+/// Bereich aus `a und b`, z.B.: `0 und 2`.
+fn read_interval<'a>(bounds: &'a str, settings: &Settings) -> Result<(f64, f64), Error<'a>> {
+    let (start, end) = match bounds.split_once(" und ") {
+        Some(bounds) => bounds,
+        None => return Err(Error::Evaluate("\"zwischen\" erwartet \"und\"".to_string()))
+    };
+    let mut res = [0f64; 2];
+    for (idx, bound) in [start, end].into_iter().enumerate() {
+        let tree = parse(bound, settings)?;
+        let value = tree.evaluate(settings).map_err(|e| Error::TokenStream(bound, vec![e]))?;
+        res[idx] = value.number("Bereich").map_err(Error::Evaluate)?;
+    }
+    Ok((res[0].min(res[1]), res[0].max(res[1])))
+}
+
+/// Baum der Eingabe mit Stelle und Wert jedes Knotens.
+pub fn calculate_tree<'a>(input: &'a str, settings: &Settings, format: Dump) -> Result<String, Error<'a>> {
+    let tree = parse(input, settings)?;
+    Ok(tree.dump(input, settings, format))
+}
+
+/// Zerlegt die Eingabe und erstellt den Baum innerhalb der [`Limits`](settings::Limits).
+pub fn parse<'a>(input: &'a str, settings: &Settings) -> Result<ParseTree, Error<'a>> {
+    parse_with(input, &[], settings)
+}
+
+/// Wie [`parse`], die Wörter in `variables` werden zu Variablen, siehe [`CompiledExpr`].
+pub fn parse_with<'a>(input: &'a str, variables: &[&'static str], settings: &Settings) -> Result<ParseTree, Error<'a>> {
+    let limits = settings.limits;
+    if input.chars().count() > limits.input {
+        return Err(Error::InputTooLong(limits.input))
+    }
+    let mut parse_stream = ParseStream::with_variables(input, variables)?;
+    if settings.implicit_multiplication {
+        parse_stream.insert_implicit_multiplication();
+    }
+    if parse_stream.len() > limits.tokens {
+        return Err(Error::TooManyTokens(limits.tokens))
+    }
+    parse_stream.validate()?;
+    let tree = ParseTree::limited(&parse_stream[..], limits.depth)?;
+    // Ohne eigene Funktionen wird jeder Knoten höchstens einmal ausgewertet
+    if tree.size() > limits.steps {
+        return Err(Error::TooManySteps(limits.steps))
     }
+    Ok(tree)
 }
 
 #[cfg(test)]
@@ -0,0 +1,179 @@
+//! Symbolische Ableitung nach einer Variablen, z.B.: `x^2 × sin(x)` nach `x`
+//! ergibt `2 × x × sin(x) + x^2 × cos(x)`. Auf die Knoten werden Summen-,
+//! Produkt-, Quotienten- und Kettenregel angewendet, das Ergebnis ist noch
+//! nicht zusammengefasst, siehe [`ParseTree::derive`].
+
+use super::super::types::{Operator, PrefixOperator, PostfixOperator, Function, StartEnd, Variable};
+use super::super::error::ErrorStruct;
+use super::super::settings::Style;
+use super::node::ParseNode;
+use super::print;
+use super::tree::ParseTree;
+
+pub fn derive(tree: &ParseTree, variable: Variable) -> Result<ParseTree, ErrorStruct> {
+    let span = tree.span();
+    // Constant with respect to `variable`, e.g. `y`, `ggT(4; 6)` or `3 m`
+    if !depends(tree, variable) {
+        return Ok(ParseTree::Integer(0, span))
+    }
+    match tree {
+        ParseTree::Variable(_, _) => Ok(ParseTree::Integer(1, span)),
+        ParseTree::Node(node) => derive_node(node, variable),
+        ParseTree::Prefix(PrefixOperator::Minus, inner, _) => Ok(minus(derive(inner, variable)?, span)),
+        ParseTree::Postfix(PostfixOperator::Percent, inner, _) => Ok(node(Operator::Divide, derive(inner, variable)?, integer(100, span), span)),
+        ParseTree::Postfix(PostfixOperator::Permille, inner, _) => Ok(node(Operator::Divide, derive(inner, variable)?, integer(1000, span), span)),
+        ParseTree::Function(function, args, _) if function.is_unary() => derive_function(*function, &args[0], variable, span),
+        // Piecewise, the condition itself is kept
+        ParseTree::Condition(condition, then, otherwise, _) => {
+            let then = derive(then, variable)?;
+            let otherwise = derive(otherwise, variable)?;
+            Ok(ParseTree::Condition(condition.clone(), Box::new(then), Box::new(otherwise), span))
+        }
+        tree => Err(unsupported(tree))
+    }
+}
+
+/// Die Kette `x + 2x - 3` wird von unten ohne Rekursion abgeleitet, jeder
+/// Knoten mit der schon berechneten Ableitung seines linken Operanden.
+fn derive_node(binary: &ParseNode, variable: Variable) -> Result<ParseTree, ErrorStruct> {
+    let (first, nodes) = binary.chain();
+    let mut left_depends = depends(first, variable);
+    let mut derivative = derive(first, variable)?;
+    for node in nodes {
+        let right_depends = depends(node.right(), variable);
+        derivative = match left_depends || right_depends {
+            true => derive_step(node, derivative, left_depends, variable)?,
+            false => ParseTree::Integer(0, node.span()),
+        };
+        left_depends = left_depends || right_depends;
+    }
+    Ok(derivative)
+}
+
+/// Ableitung von `u ∘ v` mit der Ableitung `du` von `u`, `u_depends` gibt an
+/// ob `u` von `variable` abhängt.
+fn derive_step(binary: &ParseNode, du: ParseTree, u_depends: bool, variable: Variable) -> Result<ParseTree, ErrorStruct> {
+    use Operator::*;
+
+    let span = binary.span();
+    let (u, v) = (binary.left(), binary.right());
+    match binary.operator() {
+        // `a + b%` is `a × (1 + b ÷ 100)`
+        Plus |
+        Minus if matches!(v, ParseTree::Postfix(PostfixOperator::Percent | PostfixOperator::Permille, _, _)) => {
+            let (inner, divisor) = match v {
+                ParseTree::Postfix(PostfixOperator::Percent, inner, _) => (inner, 100),
+                ParseTree::Postfix(_, inner, _) => (inner, 1000),
+                _ => unreachable!()
+            };
+            let factor = node(binary.operator(), integer(1, span), node(Divide, (**inner).clone(), integer(divisor, span), span), span);
+            let left = node(Multiply, du, factor.clone(), span);
+            let right = node(Multiply, u.clone(), derive(&factor, variable)?, span);
+            Ok(node(Plus, left, right, span))
+        }
+        Plus |
+        Minus => Ok(node(binary.operator(), du, derive(v, variable)?, span)),
+        // (u × v)' = u' × v + u × v'
+        Multiply |
+        ImplicitMultiply => {
+            let left = node(Multiply, du, v.clone(), span);
+            let right = node(Multiply, u.clone(), derive(v, variable)?, span);
+            Ok(node(Plus, left, right, span))
+        }
+        // (u ÷ v)' = (u' × v - u × v') ÷ v^2
+        Divide => {
+            let left = node(Multiply, du, v.clone(), span);
+            let right = node(Multiply, u.clone(), derive(v, variable)?, span);
+            let denominator = node(Power, v.clone(), integer(2, span), span);
+            Ok(node(Divide, node(Minus, left, right, span), denominator, span))
+        }
+        // (u^n)' = n × u^(n - 1) × u'
+        Power if !depends(v, variable) => {
+            let exponent = node(Minus, v.clone(), integer(1, span), span);
+            let outer = node(Multiply, v.clone(), node(Power, u.clone(), exponent, span), span);
+            Ok(node(Multiply, outer, du, span))
+        }
+        // (a^v)' = a^v × ln(a) × v'
+        Power if !u_depends => {
+            let outer = node(Multiply, ParseTree::Node(Box::new(binary.clone())), function(Function::Ln, u.clone(), span), span);
+            Ok(node(Multiply, outer, derive(v, variable)?, span))
+        }
+        // (u^v)' = u^v × (v' × ln(u) + v × u' ÷ u)
+        Power => {
+            let left = node(Multiply, derive(v, variable)?, function(Function::Ln, u.clone(), span), span);
+            let right = node(Divide, node(Multiply, v.clone(), du, span), u.clone(), span);
+            Ok(node(Multiply, ParseTree::Node(Box::new(binary.clone())), node(Plus, left, right, span), span))
+        }
+        _ => Err(unsupported(&ParseTree::Node(Box::new(binary.clone()))))
+    }
+}
+
+/// Kettenregel, die äußere Ableitung an der Stelle `u` mal `u'`.
+fn derive_function(f: Function, u: &ParseTree, variable: Variable, span: StartEnd) -> Result<ParseTree, ErrorStruct> {
+    let outer = match f {
+        Function::Sin => function(Function::Cos, u.clone(), span),
+        Function::Cos => minus(function(Function::Sin, u.clone(), span), span),
+        Function::Tan => {
+            let cos = node(Operator::Power, function(Function::Cos, u.clone(), span), integer(2, span), span);
+            node(Operator::Divide, integer(1, span), cos, span)
+        }
+        Function::Exp => function(Function::Exp, u.clone(), span),
+        Function::Ln => node(Operator::Divide, integer(1, span), u.clone(), span),
+        Function::Sqrt => {
+            let sqrt = node(Operator::Multiply, integer(2, span), function(Function::Sqrt, u.clone(), span), span);
+            node(Operator::Divide, integer(1, span), sqrt, span)
+        }
+        Function::Gcd |
+        Function::Lcm |
+        Function::Between => unreachable!()
+    };
+    Ok(node(Operator::Multiply, outer, derive(u, variable)?, span))
+}
+
+/// Ob der Ausdruck von `variable` abhängt.
+fn depends(tree: &ParseTree, variable: Variable) -> bool {
+    match tree {
+        ParseTree::Variable(v, _) => *v == variable,
+        ParseTree::Node(node) => {
+            let (first, nodes) = node.chain();
+            depends(first, variable) || nodes.iter().any(|node| depends(node.right(), variable))
+        }
+        ParseTree::Prefix(_, tree, _) |
+        ParseTree::Postfix(_, tree, _) => depends(tree, variable),
+        ParseTree::Function(_, args, _) => args.iter().any(|arg| depends(arg, variable)),
+        ParseTree::Condition(condition, then, otherwise, _) => {
+            depends(condition, variable) || depends(then, variable) || depends(otherwise, variable)
+        }
+        _ => false
+    }
+}
+
+/// Fehler für einen Teilausdruck der sich nicht ableiten lässt, z.B.: `x!`.
+fn unsupported(tree: &ParseTree) -> ErrorStruct {
+    let msg = match tree {
+        ParseTree::Node(node) if node.operator() == Operator::Compound => "Ableitung mit Einheit nicht möglich".to_string(),
+        ParseTree::Node(node) => format!("Ableitung mit \"{}\" nicht möglich", print::operator(node.operator(), Style::Unicode)),
+        ParseTree::Prefix(PrefixOperator::Not, _, _) => "Ableitung mit \"nicht\" nicht möglich".to_string(),
+        ParseTree::Postfix(PostfixOperator::Factorial, _, _) => "Ableitung mit \"!\" nicht möglich".to_string(),
+        ParseTree::Postfix(PostfixOperator::Currency(_), _, _) => "Ableitung mit Währung nicht möglich".to_string(),
+        ParseTree::Function(function, _, _) => format!("Ableitung mit \"{}\" nicht möglich", function.name()),
+        _ => "Ableitung mit Einheit nicht möglich".to_string(),
+    };
+    ErrorStruct::new(tree.span(), msg)
+}
+
+fn node(operator: Operator, left: ParseTree, right: ParseTree, span: StartEnd) -> ParseTree {
+    ParseTree::Node(Box::new(ParseNode::new(operator, span, span, left, right)))
+}
+
+fn function(function: Function, arg: ParseTree, span: StartEnd) -> ParseTree {
+    ParseTree::Function(function, vec![arg], span)
+}
+
+fn minus(tree: ParseTree, span: StartEnd) -> ParseTree {
+    ParseTree::Prefix(PrefixOperator::Minus, Box::new(tree), span)
+}
+
+fn integer(n: i64, span: StartEnd) -> ParseTree {
+    ParseTree::Integer(n, span)
+}
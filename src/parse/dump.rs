@@ -0,0 +1,190 @@
+//! Darstellung eines [`ParseTree`] als eingerückter Baum oder im DOT Format
+//! von Graphviz. Jeder Knoten zeigt seinen Operator, die Stelle seines
+//! Teilausdrucks in der Eingabe und seinen Wert, z.B.: `× 8..17 "3 mal -6" = -18`.
+
+use super::super::types::{Operator, PrefixOperator, PostfixOperator, StartEnd};
+use super::super::settings::{Settings, Style, Notation};
+use super::super::value::Value;
+use super::super::format;
+use super::super::currency;
+use super::super::unit;
+use super::print;
+use super::trace::Trace;
+use super::tree::ParseTree;
+
+/// Format der Ausgabe von [`ParseTree::dump`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Dump {
+    /// Eingerückter Baum mit `├──` und `└──`.
+    Ascii,
+    /// Graphviz, z.B.: `term-calc --dump-tree=dot "1+2" | dot -Tpng`.
+    Dot,
+}
+
+/// Knoten mit allem was ausgegeben wird, die Nummer ist der Index in der
+/// Liste und die aus [`Trace`].
+struct Item {
+    label: String,
+    span: String,
+    value: String,
+    children: Vec<usize>,
+}
+
+pub fn dump(tree: &ParseTree, input: &str, settings: &Settings, format: Dump) -> String {
+    let mut trace = Trace::all();
+    let error = tree.evaluate_traced(settings, &mut trace).err();
+
+    let mut settings = settings.clone();
+    settings.notation = Notation::Shortest;
+    let mut items = Vec::new();
+    let root = collect(tree, input, &trace, &settings, &mut items);
+
+    let mut out = String::new();
+    match format {
+        Dump::Ascii => {
+            ascii(&items, root, &mut out);
+            if let Some(e) = error {
+                out.push_str(format!("Fehler bei {}: {}\n", span(e.range(), input), e.msg()).as_str());
+            }
+        }
+        Dump::Dot => {
+            out.push_str("digraph ParseTree {\n");
+            out.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+            dot(&items, root, &mut out);
+            out.push_str("}\n");
+        }
+    }
+    out
+}
+
+/// Baut die Knoten in Postorder auf, somit stimmen die Nummern mit der
+/// Auswertung überein.
+fn collect(tree: &ParseTree, input: &str, trace: &Trace, settings: &Settings, items: &mut Vec<Item>) -> usize {
+    let children = match tree {
+        ParseTree::Number(_, _) |
+        ParseTree::Integer(_, _) |
+        ParseTree::Decimal(_, _) |
+        ParseTree::Unit(_, _) |
+        ParseTree::Currency(_, _) |
+        ParseTree::Date(_, _) |
+        ParseTree::Variable(_, _) => Vec::new(),
+        // The chain `1 + 2 - 3` is collected from the bottom without recursion
+        ParseTree::Node(node) => {
+            let (first, nodes) = node.chain();
+            let mut left = collect(first, input, trace, settings, items);
+            let last = nodes.len() -1;
+            for node in &nodes[..last] {
+                let right = collect(node.right(), input, trace, settings, items);
+                left = push(items, operator(node.operator()), span(node.span(), input), vec![left, right], trace, settings);
+            }
+            vec![left, collect(nodes[last].right(), input, trace, settings, items)]
+        }
+        ParseTree::Prefix(_, tree, _) |
+        ParseTree::Postfix(_, tree, _) => vec![collect(tree, input, trace, settings, items)],
+        ParseTree::Function(_, args, _) => args.iter().map(|arg| collect(arg, input, trace, settings, items)).collect(),
+        ParseTree::Condition(condition, then, otherwise, _) => vec![
+            collect(condition, input, trace, settings, items),
+            collect(then, input, trace, settings, items),
+            collect(otherwise, input, trace, settings, items),
+        ],
+    };
+    push(items, label(tree), span(tree.span(), input), children, trace, settings)
+}
+
+/// Fügt den nächsten Knoten mit seinem Wert aus `trace` an.
+fn push(items: &mut Vec<Item>, label: String, span: String, children: Vec<usize>, trace: &Trace, settings: &Settings) -> usize {
+    let id = items.len();
+    let value = match trace.value(id) {
+        Some(Value::Number(n)) => format::format_number(*n, settings),
+        Some(value) => format::format_value(value, settings),
+        None => "nicht berechnet".to_string()
+    };
+    items.push(Item {
+        label,
+        span,
+        value,
+        children,
+    });
+    id
+}
+
+fn label(tree: &ParseTree) -> String {
+    match tree {
+        ParseTree::Number(n, _) => format!("{}", n),
+        ParseTree::Integer(n, _) => format!("{}", n),
+        ParseTree::Decimal(d, _) => d.to_string(),
+        ParseTree::Unit(idx, _) => unit::UNITS[*idx].symbol.to_string(),
+        ParseTree::Currency(idx, _) => currency::display(*idx).to_string(),
+        ParseTree::Date(date, _) => date.to_string(),
+        ParseTree::Variable(variable, _) => variable.name.to_string(),
+        ParseTree::Node(node) => operator(node.operator()),
+        ParseTree::Prefix(PrefixOperator::Minus, _, _) => "-".to_string(),
+        ParseTree::Prefix(PrefixOperator::Not, _, _) => "nicht".to_string(),
+        ParseTree::Postfix(op, _, _) => {
+            match op {
+                PostfixOperator::Percent => "%".to_string(),
+                PostfixOperator::Permille => "‰".to_string(),
+                PostfixOperator::Factorial => "!".to_string(),
+                PostfixOperator::Unit(idx) => unit::UNITS[*idx].symbol.to_string(),
+                PostfixOperator::Currency(idx) => currency::display(*idx).to_string(),
+            }
+        }
+        ParseTree::Function(function, _, _) => function.name().to_string(),
+        ParseTree::Condition(_, _, _, _) => "wenn".to_string(),
+    }
+}
+
+/// Stelle in der Eingabe mit dem Text, z.B.: `12..15 "mal"`.
+fn span(range: StartEnd, input: &str) -> String {
+    let text = input.get(range.start..range.end).unwrap_or_default();
+    format!("{}..{} \"{}\"", range.start, range.end, text)
+}
+
+fn line(item: &Item) -> String {
+    format!("{}  {}  = {}", item.label, item.span, item.value)
+}
+
+fn operator(operator: Operator) -> String {
+    match operator {
+        Operator::ImplicitMultiply => "× (implizit)".to_string(),
+        Operator::Compound => "zusammengesetzt".to_string(),
+        op => print::operator(op, Style::Unicode).to_string(),
+    }
+}
+
+/// Gibt die Knoten in Preorder aus, mit einem Stack statt Rekursion da eine
+/// lange Summe eine Ebene pro Summand hat.
+fn ascii(items: &[Item], root: usize, out: &mut String) {
+    let mut stack = vec![(root, String::new(), String::new())];
+    while let Some((id, head, indent)) = stack.pop() {
+        let item = &items[id];
+        out.push_str(format!("{}{}\n", head, line(item)).as_str());
+        let len = item.children.len();
+        for (idx, child) in item.children.iter().enumerate().rev() {
+            let last = idx +1 == len;
+            let (branch, next) = match last {
+                true => ("└── ", "    "),
+                false => ("├── ", "│   "),
+            };
+            stack.push((*child, format!("{}{}", indent, branch), format!("{}{}", indent, next)));
+        }
+    }
+}
+
+fn dot(items: &[Item], root: usize, out: &mut String) {
+    let mut stack = vec![root];
+    while let Some(id) = stack.pop() {
+        let item = &items[id];
+        let label = [item.label.clone(), item.span.clone(), format!("= {}", item.value)];
+        let label: Vec<String> = label.iter().map(|line| escape(line)).collect();
+        out.push_str(format!("    n{} [label=\"{}\"];\n", id, label.join("\\n")).as_str());
+        for child in &item.children {
+            out.push_str(format!("    n{} -> n{};\n", id, child).as_str());
+        }
+        stack.extend(item.children.iter().rev());
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,186 @@
+//! Exakte Auswertung mit [`i64`] für `exakt an`. Besteht ein Ausdruck nur
+//! aus ganzen Zahlen, wird ohne Umweg über [`f64`] gerechnet und ein
+//! Überlauf ergibt einen Fehler statt einer gerundeten Zahl.
+
+use super::super::types::{Operator, PrefixOperator, PostfixOperator, Function};
+use super::super::error::ErrorStruct;
+use super::integer;
+use super::tree::ParseTree;
+
+/// Gibt `None` zurück wenn der Ausdruck nicht nur aus ganzen Zahlen besteht
+/// oder eine Division nicht aufgeht, z.B.: `7 / 2`.
+pub fn evaluate(tree: &ParseTree) -> Result<Option<i64>, ErrorStruct> {
+    if !integral(tree) {
+        return Ok(None)
+    }
+    exact(tree)
+}
+
+/// Alle Zahlen sind ganz und alle Operatoren ergeben wieder ganze Zahlen.
+fn integral(tree: &ParseTree) -> bool {
+    use Operator::*;
+
+    match tree {
+        ParseTree::Integer(_, _) => true,
+        ParseTree::Node(node) => {
+            let (first, nodes) = node.chain();
+            integral(first) && nodes.iter().all(|node| {
+                let operator = matches!(
+                    node.operator(),
+                    Plus | Minus | Multiply | ImplicitMultiply | Divide | Modulo | IntegerDivide |
+                    BitAnd | BitOr | BitXor | ShiftLeft | ShiftRight | Power
+                );
+                operator && integral(node.right())
+            })
+        }
+        ParseTree::Prefix(_, tree, _) => integral(tree),
+        ParseTree::Postfix(PostfixOperator::Factorial, tree, _) => integral(tree),
+        ParseTree::Function(Function::Gcd | Function::Lcm, args, _) => args.iter().all(integral),
+        _ => false
+    }
+}
+
+fn exact(tree: &ParseTree) -> Result<Option<i64>, ErrorStruct> {
+    let res = match tree {
+        ParseTree::Integer(n, _) => Some(*n),
+        // The chain `1 + 2 - 3` is calculated from the bottom without recursion
+        ParseTree::Node(node) => {
+            let (first, nodes) = node.chain();
+            let mut left = match exact(first)? {
+                Some(left) => left,
+                None => return Ok(None)
+            };
+            for node in nodes {
+                let right = match exact(node.right())? {
+                    Some(right) => right,
+                    None => return Ok(None)
+                };
+                left = match operator(node.operator(), left, right) {
+                    Ok(Some(n)) => n,
+                    Ok(None) => return Ok(None),
+                    Err(msg) => return Err(ErrorStruct::new(node.span(), msg))
+                };
+            }
+            Some(left)
+        }
+        ParseTree::Prefix(op, tree, span) => {
+            let n = match exact(tree)? {
+                Some(n) => n,
+                None => return Ok(None)
+            };
+            let res = match op {
+                PrefixOperator::Minus => n.checked_neg(),
+                PrefixOperator::Not => Some(!n),
+            };
+            match res {
+                Some(n) => Some(n),
+                None => return Err(ErrorStruct::new(*span, "Zahl zu groß".to_string()))
+            }
+        }
+        ParseTree::Postfix(_, tree, span) => {
+            let n = match exact(tree)? {
+                Some(n) => n,
+                None => return Ok(None)
+            };
+            match factorial(n) {
+                Ok(n) => Some(n),
+                Err(msg) => return Err(ErrorStruct::new(*span, msg))
+            }
+        }
+        ParseTree::Function(function, args, span) => {
+            let mut values = Vec::with_capacity(args.len());
+            for arg in args {
+                match exact(arg)? {
+                    Some(n) => values.push(n),
+                    None => return Ok(None)
+                }
+            }
+            let res = match function {
+                Function::Gcd => gcd(&values),
+                _ => lcm(&values),
+            };
+            match res {
+                Ok(n) => Some(n),
+                Err(msg) => return Err(ErrorStruct::new(*span, msg))
+            }
+        }
+        _ => None
+    };
+    Ok(res)
+}
+
+/// `None` wenn eine Division nicht aufgeht oder der Exponent negativ ist.
+fn operator(operator: Operator, left: i64, right: i64) -> Result<Option<i64>, String> {
+    use Operator::*;
+
+    let res = match operator {
+        Plus => left.checked_add(right),
+        Minus => left.checked_sub(right),
+        Multiply |
+        ImplicitMultiply => left.checked_mul(right),
+        Divide |
+        IntegerDivide if right == 0 => return Err("Teilen durch Null nicht möglich".to_string()),
+        Divide if left.checked_rem(right).is_some_and(|rest| rest != 0) => return Ok(None),
+        Divide => left.checked_div(right),
+        IntegerDivide => left.checked_div_euclid(right),
+        Modulo if right == 0 => return Err("Modulo durch Null nicht möglich".to_string()),
+        Modulo => left.checked_rem_euclid(right),
+        BitAnd => Some(left & right),
+        BitOr => Some(left | right),
+        BitXor => Some(left ^ right),
+        ShiftLeft |
+        ShiftRight if !(0..64).contains(&right) => return Err("Verschiebung nur um 0 bis 63 Bit möglich".to_string()),
+        ShiftLeft => Some(left << right),
+        ShiftRight => Some(left >> right),
+        Power if right < 0 => return Ok(None),
+        Power => u32::try_from(right).ok().and_then(|exponent| left.checked_pow(exponent)),
+        _ => unreachable!()
+    };
+    match res {
+        Some(n) => Ok(Some(n)),
+        None => Err("Zahl zu groß".to_string())
+    }
+}
+
+fn factorial(n: i64) -> Result<i64, String> {
+    if n < 0 {
+        return Err("Fakultät von negativen Zahlen nicht möglich".to_string())
+    }
+    let mut res: i64 = 1;
+    for i in 2..=n {
+        res = match res.checked_mul(i) {
+            Some(res) => res,
+            None => return Err(format!("Fakultät zu groß: {}! passt nicht in 64 Bit", n))
+        }
+    }
+    Ok(res)
+}
+
+fn gcd(args: &[i64]) -> Result<i64, String> {
+    let mut res = 0;
+    for n in args {
+        res = match integer::gcd_pair(res, *n) {
+            Some(res) => res,
+            None => return Err("Zahl zu groß".to_string())
+        };
+    }
+    Ok(res)
+}
+
+fn lcm(args: &[i64]) -> Result<i64, String> {
+    let mut res: i64 = 1;
+    for n in args {
+        if *n == 0 {
+            return Ok(0)
+        }
+        let factor = match (n.checked_abs(), integer::gcd_pair(res, *n)) {
+            (Some(n), Some(gcd)) => n / gcd,
+            _ => return Err("Zahl zu groß".to_string())
+        };
+        res = match res.checked_mul(factor) {
+            Some(res) => res,
+            None => return Err("kgV: Ergebnis zu groß".to_string())
+        };
+    }
+    Ok(res)
+}
@@ -0,0 +1,259 @@
+//! Ausgabe eines [`ParseTree`] als LaTeX oder MathML, z.B.: `1 / (2 + 3)`
+//! als `\frac{1}{2 + 3}` oder `<mfrac><mn>1</mn><mrow>…</mrow></mfrac>`.
+
+use super::super::types::{Operator, PrefixOperator, PostfixOperator, Function};
+use super::super::settings::Export;
+use super::super::currency;
+use super::super::unit;
+use super::print::{self, PREFIX, POWER, POSTFIX, ATOM};
+use super::node::ParseNode;
+use super::tree::ParseTree;
+
+/// Teil eines Ausdrucks, bei MathML immer genau ein Element.
+struct Part {
+    text: String,
+    precedence: u8,
+    negative: bool,
+}
+
+pub fn export(tree: &ParseTree, format: Export) -> String {
+    let text = Exporter { format }.tree(tree).text;
+    match format {
+        Export::Latex => text,
+        Export::MathMl => format!("<math xmlns=\"http://www.w3.org/1998/Math/MathML\">{}</math>", text),
+    }
+}
+
+struct Exporter {
+    format: Export,
+}
+
+impl Exporter {
+    fn tree(&self, tree: &ParseTree) -> Part {
+        match tree {
+            ParseTree::Number(n, _) => self.number(*n),
+            ParseTree::Integer(n, _) => self.literal(n.unsigned_abs().to_string(), *n < 0),
+            ParseTree::Decimal(d, _) => self.literal(d.to_string().trim_start_matches('-').to_string(), d.is_negative()),
+            ParseTree::Unit(idx, _) => atom(self.unit(*idx)),
+            ParseTree::Currency(idx, _) => atom(self.text(currency::display(*idx))),
+            ParseTree::Date(date, _) => atom(self.text(date.to_string().as_str())),
+            ParseTree::Variable(variable, _) => atom(self.variable(variable.name)),
+            // The chain `1 + 2 - 3` is exported from the bottom without recursion
+            ParseTree::Node(node) => {
+                let (first, nodes) = node.chain();
+                let mut part = self.tree(first);
+                for node in nodes {
+                    let right = self.tree(node.right());
+                    part = self.node(node, part, right);
+                }
+                part
+            }
+            ParseTree::Prefix(op, tree, _) => {
+                let tree = self.wrap(self.tree(tree), PREFIX, true);
+                let text = match (op, self.format) {
+                    (PrefixOperator::Minus, Export::Latex) => format!("-{}", tree),
+                    (PrefixOperator::Minus, Export::MathMl) => format!("<mrow><mo>-</mo>{}</mrow>", tree),
+                    (PrefixOperator::Not, Export::Latex) => format!("\\lnot {}", tree),
+                    (PrefixOperator::Not, Export::MathMl) => format!("<mrow><mo>¬</mo>{}</mrow>", tree),
+                };
+                Part { text, precedence: PREFIX, negative: false }
+            }
+            ParseTree::Postfix(op, tree, _) => {
+                let tree = self.wrap(self.tree(tree), POSTFIX, true);
+                let suffix = match (op, self.format) {
+                    (PostfixOperator::Percent, Export::Latex) => "\\,\\%".to_string(),
+                    (PostfixOperator::Permille, Export::Latex) => "\\,\\text{‰}".to_string(),
+                    (PostfixOperator::Factorial, Export::Latex) => "!".to_string(),
+                    (PostfixOperator::Percent, Export::MathMl) => "<mo>%</mo>".to_string(),
+                    (PostfixOperator::Permille, Export::MathMl) => "<mo>‰</mo>".to_string(),
+                    (PostfixOperator::Factorial, Export::MathMl) => "<mo>!</mo>".to_string(),
+                    (PostfixOperator::Unit(idx), Export::Latex) => format!("\\,{}", self.unit(*idx)),
+                    (PostfixOperator::Currency(idx), Export::Latex) => format!("\\,{}", self.text(currency::display(*idx))),
+                    (PostfixOperator::Unit(idx), Export::MathMl) => format!("<mspace width=\"0.2em\"/>{}", self.unit(*idx)),
+                    (PostfixOperator::Currency(idx), Export::MathMl) => format!("<mspace width=\"0.2em\"/>{}", self.text(currency::display(*idx))),
+                };
+                let text = match self.format {
+                    Export::Latex => format!("{}{}", tree, suffix),
+                    Export::MathMl => format!("<mrow>{}{}</mrow>", tree, suffix),
+                };
+                Part { text, precedence: POSTFIX, negative: false }
+            }
+            ParseTree::Function(Function::Between, args, _) => {
+                let args: Vec<String> = args.iter().map(|arg| self.tree(arg).text).collect();
+                let text = match self.format {
+                    Export::Latex => format!("\\text{{zwischen }} {}", args.join(" \\text{ und } ")),
+                    Export::MathMl => format!("<mrow><mtext>zwischen</mtext>{}</mrow>", args.join("<mtext>und</mtext>")),
+                };
+                Part { text, precedence: 0, negative: false }
+            }
+            ParseTree::Function(function, args, _) => {
+                let args: Vec<String> = args.iter().map(|arg| self.tree(arg).text).collect();
+                let text = match self.format {
+                    Export::Latex => format!("\\operatorname{{{}}}\\left({}\\right)", function.name(), args.join("; ")),
+                    Export::MathMl => format!("<mrow><mi>{}</mi><mo>&#x2061;</mo><mrow><mo>(</mo>{}<mo>)</mo></mrow></mrow>", function.name(), args.join("<mo>;</mo>")),
+                };
+                atom(text)
+            }
+            ParseTree::Condition(condition, then, otherwise, _) => {
+                let condition = self.tree(condition).text;
+                let then = self.tree(then).text;
+                let otherwise = self.tree(otherwise).text;
+                let text = match self.format {
+                    Export::Latex => format!("\\begin{{cases}} {} & \\text{{wenn }} {} \\\\ {} & \\text{{sonst}} \\end{{cases}}", then, condition, otherwise),
+                    Export::MathMl => format!(
+                        "<mrow><mo>{{</mo><mtable><mtr><mtd>{}</mtd><mtd><mtext>wenn</mtext>{}</mtd></mtr><mtr><mtd>{}</mtd><mtd><mtext>sonst</mtext></mtd></mtr></mtable></mrow>",
+                        then, condition, otherwise
+                    ),
+                };
+                atom(text)
+            }
+        }
+    }
+
+    fn node(&self, node: &ParseNode, left: Part, right: Part) -> Part {
+        match node.operator() {
+            Operator::Divide => {
+                let text = match self.format {
+                    Export::Latex => format!("\\frac{{{}}}{{{}}}", left.text, right.text),
+                    Export::MathMl => format!("<mfrac>{}{}</mfrac>", left.text, right.text),
+                };
+                atom(text)
+            }
+            // The exponent is raised and needs no parentheses
+            Operator::Power => {
+                let left = self.wrap(left, POWER, true);
+                let text = match self.format {
+                    Export::Latex => format!("{}^{{{}}}", left, right.text),
+                    Export::MathMl => format!("<msup>{}{}</msup>", left, right.text),
+                };
+                Part { text, precedence: POWER, negative: false }
+            }
+            operator => {
+                let precedence = print::precedence(operator);
+                let left = self.wrap(left, precedence, false);
+                let right = self.wrap(right, precedence, true);
+                let text = match self.format {
+                    Export::Latex => format!("{} {} {}", left, self.operator(operator), right),
+                    Export::MathMl => format!("<mrow>{}{}{}</mrow>", left, self.operator(operator), right),
+                };
+                Part { text, precedence, negative: false }
+            }
+        }
+    }
+
+    fn number(&self, n: f64) -> Part {
+        self.literal(format!("{}", n.abs()), n.is_sign_negative())
+    }
+
+    fn literal(&self, literal: String, negative: bool) -> Part {
+        let text = match self.format {
+            Export::Latex => literal.replace('.', "{,}"),
+            Export::MathMl => format!("<mn>{}</mn>", literal.replace('.', ",")),
+        };
+        match (negative, self.format) {
+            (false, _) => atom(text),
+            (true, Export::Latex) => Part { text: format!("-{}", text), precedence: ATOM, negative: true },
+            (true, Export::MathMl) => Part { text: format!("<mrow><mo>-</mo>{}</mrow>", text), precedence: ATOM, negative: true },
+        }
+    }
+
+    fn unit(&self, idx: usize) -> String {
+        let symbol = unit::UNITS[idx].symbol;
+        match self.format {
+            Export::Latex => format!("\\mathrm{{{}}}", symbol),
+            Export::MathMl => format!("<mi mathvariant=\"normal\">{}</mi>", escape(symbol)),
+        }
+    }
+
+    fn variable(&self, name: &str) -> String {
+        match self.format {
+            Export::Latex => name.to_string(),
+            Export::MathMl => format!("<mi>{}</mi>", escape(name)),
+        }
+    }
+
+    fn text(&self, text: &str) -> String {
+        match self.format {
+            Export::Latex => format!("\\text{{{}}}", text.replace('$', "\\$")),
+            Export::MathMl => format!("<mtext>{}</mtext>", escape(text)),
+        }
+    }
+
+    fn operator(&self, operator: Operator) -> String {
+        use Operator::*;
+
+        let latex = match operator {
+            Plus => "+",
+            Minus => "-",
+            Multiply |
+            ImplicitMultiply => "\\cdot",
+            Divide => "/",
+            Modulo => "\\bmod",
+            IntegerDivide => "\\operatorname{div}",
+            BitAnd => "\\mathbin{\\&}",
+            BitOr => "\\mathbin{|}",
+            BitXor => "\\oplus",
+            ShiftLeft => "\\ll",
+            ShiftRight => "\\gg",
+            Convert => "\\text{ in }",
+            Less => "<",
+            Greater => ">",
+            LessEqual => "\\leq",
+            GreaterEqual => "\\geq",
+            Equal => "=",
+            NotEqual => "\\neq",
+            And => "\\land",
+            Or => "\\lor",
+            Compound => "\\;",
+            Power => "^",
+        };
+        let mathml = match operator {
+            Plus => "+",
+            Minus => "-",
+            Multiply |
+            ImplicitMultiply => "⋅",
+            Divide => "/",
+            Modulo => "mod",
+            IntegerDivide => "div",
+            BitAnd => "&amp;",
+            BitOr => "|",
+            BitXor => "⊕",
+            ShiftLeft => "≪",
+            ShiftRight => "≫",
+            Convert => "in",
+            Less => "&lt;",
+            Greater => "&gt;",
+            LessEqual => "≤",
+            GreaterEqual => "≥",
+            Equal => "=",
+            NotEqual => "≠",
+            And => "∧",
+            Or => "∨",
+            Compound => "",
+            Power => "^",
+        };
+        match self.format {
+            Export::Latex => latex.to_string(),
+            Export::MathMl if operator == Compound => "<mspace width=\"0.3em\"/>".to_string(),
+            Export::MathMl => format!("<mo>{}</mo>", mathml),
+        }
+    }
+
+    /// Klammert wie [`print`](super::print) mit `\left( \right)` bzw. `<mo>(</mo>`.
+    fn wrap(&self, part: Part, precedence: u8, right: bool) -> String {
+        let needed = part.negative || part.precedence < precedence || (right && part.precedence == precedence);
+        match (needed, self.format) {
+            (false, _) => part.text,
+            (true, Export::Latex) => format!("\\left({}\\right)", part.text),
+            (true, Export::MathMl) => format!("<mrow><mo>(</mo>{}<mo>)</mo></mrow>", part.text),
+        }
+    }
+}
+
+fn atom(text: String) -> Part {
+    Part { text, precedence: ATOM, negative: false }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,81 @@
+//! Faltet konstante Teilausdrücke für [`CompiledExpr`](crate::CompiledExpr).
+//! Nur Ergebnisse welche eine Zahl sind werden ersetzt, Einheiten, Währungen
+//! und Wahrheitswerte bleiben als Teilbaum erhalten.
+
+use super::super::types::PostfixOperator;
+use super::super::value::Value;
+use super::super::settings::Settings;
+use super::node::ParseNode;
+use super::tree::ParseTree;
+
+pub fn fold(tree: ParseTree, settings: &Settings) -> ParseTree {
+    let tree = match tree {
+        // The chain `1 + 2 × x - 3` is folded from the bottom without recursion
+        ParseTree::Node(node) => {
+            let mut nodes = Vec::new();
+            let mut left = ParseTree::Node(node);
+            while let ParseTree::Node(node) = left {
+                let (operator, range, span, inner, right) = node.into_parts();
+                nodes.push((operator, range, span, right));
+                left = inner;
+            }
+            let mut left = fold(left, settings);
+            for (operator, range, span, right) in nodes.into_iter().rev() {
+                let right = fold(right, settings);
+                left = constant(ParseTree::Node(Box::new(ParseNode::new(operator, range, span, left, right))), settings);
+            }
+            return left
+        }
+        ParseTree::Prefix(op, tree, span) => ParseTree::Prefix(op, Box::new(fold(*tree, settings)), span),
+        ParseTree::Postfix(op, tree, span) => ParseTree::Postfix(op, Box::new(fold(*tree, settings)), span),
+        ParseTree::Function(function, args, span) => {
+            let args = args.into_iter().map(|arg| fold(arg, settings)).collect();
+            ParseTree::Function(function, args, span)
+        }
+        ParseTree::Condition(condition, then, otherwise, span) => {
+            let condition = fold(*condition, settings);
+            let then = fold(*then, settings);
+            let otherwise = fold(*otherwise, settings);
+            ParseTree::Condition(Box::new(condition), Box::new(then), Box::new(otherwise), span)
+        }
+        tree => tree
+    };
+    constant(tree, settings)
+}
+
+/// Ersetzt den Teilbaum durch seinen Wert, falls alle direkten Teilbäume
+/// bereits Zahlen sind.
+fn constant(tree: ParseTree, settings: &Settings) -> ParseTree {
+    if !foldable(&tree) {
+        return tree
+    }
+    match tree.evaluate(settings) {
+        Ok(Value::Number(n)) => number(n, &tree),
+        _ => tree
+    }
+}
+
+/// Alle direkten Teilbäume sind bereits Zahlen. Prozent und Promille bleiben
+/// stehen, `x + 10%` rechnet mit dem Prozentsatz anders als `x + 0,1`.
+fn foldable(tree: &ParseTree) -> bool {
+    match tree {
+        ParseTree::Node(node) => is_number(node.left()) && is_number(node.right()),
+        ParseTree::Prefix(_, tree, _) => is_number(tree),
+        ParseTree::Postfix(PostfixOperator::Factorial, tree, _) => is_number(tree),
+        ParseTree::Function(_, args, _) => args.iter().all(is_number),
+        _ => false
+    }
+}
+
+fn is_number(tree: &ParseTree) -> bool {
+    matches!(tree, ParseTree::Number(_, _) | ParseTree::Integer(_, _) | ParseTree::Decimal(_, _))
+}
+
+/// Ganze Zahlen bleiben für [`ParseTree::evaluate_exact`] ganz.
+fn number(n: f64, tree: &ParseTree) -> ParseTree {
+    if n.fract() == 0f64 && n.abs() <= 9_007_199_254_740_992f64 {
+        ParseTree::Integer(n as i64, tree.span())
+    } else {
+        ParseTree::Number(n, tree.span())
+    }
+}
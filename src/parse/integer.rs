@@ -0,0 +1,130 @@
+//! Ganzzahlige Rechenoperationen. Die Operanden werden intern als [`f64`]
+//! geführt, hier aber exakt als [`i64`] berechnet.
+
+use super::super::types::Operator;
+
+/// Kleinste Zahl welche nicht mehr in einen [`i64`] passt, `2^63`.
+const I64_LIMIT: f64 = 9_223_372_036_854_775_808f64;
+
+pub fn to_integer(n: f64, name: &str) -> Result<i64, String> {
+    if n.fract() != 0f64 || !n.is_finite() {
+        return Err(format!("{} nur mit ganzen Zahlen möglich", name))
+    }
+    if n.abs() >= I64_LIMIT {
+        return Err(format!("{}: Zahl zu groß für exakte Berechnung", name))
+    }
+    Ok(n as i64)
+}
+
+/// Das Ergebnis muss exakt als [`f64`] darstellbar sein.
+fn to_float(n: i64, name: &str) -> Result<f64, String> {
+    let f = n as f64;
+    if f >= I64_LIMIT || f as i64 != n {
+        return Err(format!("{}: Ergebnis nicht exakt darstellbar", name))
+    }
+    Ok(f)
+}
+
+/// Mathematischer Rest, bei positivem Teiler ist das Ergebnis nie negativ:
+/// `-7 mod 3` ergibt `2`.
+pub fn modulo(left: f64, right: f64) -> Result<f64, String> {
+    let left = to_integer(left, "Modulo")?;
+    let right = to_integer(right, "Modulo")?;
+    if right == 0 {
+        return Err("Modulo durch Null nicht möglich".to_string())
+    }
+    to_float(left.rem_euclid(right), "Modulo")
+}
+
+/// Ganzzahlige Division passend zu [`modulo`]: `-7 div 3` ergibt `-3`.
+pub fn divide(left: f64, right: f64) -> Result<f64, String> {
+    let left = to_integer(left, "Ganzzahldivision")?;
+    let right = to_integer(right, "Ganzzahldivision")?;
+    if right == 0 {
+        return Err("Teilen durch Null nicht möglich".to_string())
+    }
+    to_float(left.div_euclid(right), "Ganzzahldivision")
+}
+
+pub fn factorial(n: f64) -> Result<f64, String> {
+    let n = to_integer(n, "Fakultät")?;
+    if n < 0 {
+        return Err("Fakultät von negativen Zahlen nicht möglich".to_string())
+    }
+    let mut res: i64 = 1;
+    for i in 2..=n {
+        res = match res.checked_mul(i) {
+            Some(res) => res,
+            None => return Err(format!("Fakultät zu groß: {}! passt nicht in 64 Bit", n))
+        }
+    }
+    to_float(res, "Fakultät")
+}
+
+/// Bitweise Operationen auf 64 Bit Ganzzahlen im Zweierkomplement.
+pub fn bitwise(operator: Operator, left: f64, right: f64) -> Result<f64, String> {
+    let left = to_integer(left, "Bitoperation")?;
+    let right = to_integer(right, "Bitoperation")?;
+    let res = match operator {
+        Operator::BitAnd => left & right,
+        Operator::BitOr => left | right,
+        Operator::BitXor => left ^ right,
+        Operator::ShiftLeft |
+        Operator::ShiftRight => {
+            if !(0..64).contains(&right) {
+                return Err("Verschiebung nur um 0 bis 63 Bit möglich".to_string())
+            }
+            match operator {
+                Operator::ShiftLeft => left << right,
+                _ => left >> right
+            }
+        }
+        _ => unreachable!()
+    };
+    to_float(res, "Bitoperation")
+}
+
+pub fn not(n: f64) -> Result<f64, String> {
+    let n = to_integer(n, "Bitoperation")?;
+    to_float(!n, "Bitoperation")
+}
+
+/// `None` falls das Ergebnis `2^63` ist, z.B.: für `ggT(-2^63; 0)`.
+pub(super) fn gcd_pair(mut a: i64, mut b: i64) -> Option<i64> {
+    while b != 0 {
+        let t = a.checked_rem(b)?;
+        a = b;
+        b = t;
+    }
+    a.checked_abs()
+}
+
+pub fn gcd(args: &[f64]) -> Result<f64, String> {
+    let mut res = 0;
+    for n in args {
+        res = match gcd_pair(res, to_integer(*n, "ggT")?) {
+            Some(res) => res,
+            None => return Err("Zahl zu groß".to_string())
+        };
+    }
+    to_float(res, "ggT")
+}
+
+pub fn lcm(args: &[f64]) -> Result<f64, String> {
+    let mut res: i64 = 1;
+    for n in args {
+        let n = to_integer(*n, "kgV")?;
+        if n == 0 {
+            return Ok(0f64)
+        }
+        let factor = match (n.checked_abs(), gcd_pair(res, n)) {
+            (Some(n), Some(gcd)) => n / gcd,
+            _ => return Err("Zahl zu groß".to_string())
+        };
+        res = match res.checked_mul(factor) {
+            Some(res) => res,
+            None => return Err("kgV: Ergebnis zu groß".to_string())
+        };
+    }
+    to_float(res, "kgV")
+}
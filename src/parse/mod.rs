@@ -1,4 +1,18 @@
 mod tree;
 mod node;
+mod parser;
+mod integer;
+mod trace;
+mod print;
+mod export;
+mod dump;
+mod exact;
+mod fold;
+mod rational;
+mod simplify;
+mod derive;
+mod solve;
 
-pub use tree::*;
\ No newline at end of file
+pub use tree::*;
+pub use dump::Dump;
+pub use solve::Solutions;
\ No newline at end of file
@@ -1,170 +1,267 @@
+use std::cmp::Ordering;
+use std::mem;
 use std::str::ParseBoolError;
 
 use super::tree::ParseTree;
-use super::super::token::{ParseToken};
-use super::super::types::{Operator, StartEnd, ParseOperator, ParseOperatorKind};
-use super::super::stream::{ParseStream};
-use super::super::Error;
+use super::integer;
+use super::trace::Trace;
+use super::super::types::{Operator, PostfixOperator, StartEnd};
+use super::super::error::ErrorStruct;
+use super::super::value::Value;
+use super::super::settings::Settings;
 
 pub struct ParseNode {
     operator: Operator,
+    range: StartEnd,
+    span: StartEnd,
     left: Box<ParseTree>,
     right: Box<ParseTree>,
 } 
 
 impl ParseNode {
 
-    pub fn find_close_idx(stream: &[ParseToken], skip: usize) -> Option<usize> {
-        use ParseToken::*;
-
-        let mut count = 0;
-        for (idx, token) in stream.iter().enumerate().skip(skip) {
-            match token {
-                Open(_) => count += 1,
-                Close(_) => {
-                    count -= 1;
-                    if count == 0 {
-                        return Some(idx)
-                    }
-                }
-                _ => {}
-            }
-        }
-        None
+    pub fn operator(&self) -> Operator {
+        self.operator
     }
 
-    fn find_operators(stream: &[ParseToken]) -> (Operator, (Option<(Operator, usize)>)) {
-        use ParseToken::*;
-
-        let op1 = match &stream[1] {
-            Op((op, _)) => *op,
-            _ => panic!("Expected operator token")
-        };
-        let mut some_op2: Option<(Operator, usize)> = None;
-
-        let len = stream.len();
-        if len > 3 {
-            match &stream[2] {
-                Open(_) => {
-                    match Self::find_close_idx(&stream, 2) {
-                        Some(idx) => {
-                            if idx != len -1 {
-                                some_op2 = match &stream[idx+1] {
-                                    Op((op, _)) => Some((*op, idx+1)),
-                                    _ => panic!("Expected operator token after close paren") 
-                                }
-                            }
-                        }
-                        None => panic!("Expected to find a close paren")
-                    }
-                }
-                Number(_) => {
-                    some_op2 = match stream[3] {
-                        Op((op, _)) => Some((op, 3)),
-                        _ => panic!("Expected operator token #2")
-                    }
-                }
-                _ => panic!("Expected open or number token")
-            }
-        }
-
-        (op1, some_op2)
+    pub fn range(&self) -> StartEnd {
+        self.range
     }
 
-    pub fn from(stream: &[ParseToken]) -> Result<Self, Error> {
-        use ParseToken::*;
-        
-        let len = stream.len();
-        if len < 3 { panic!("Unexpected stream lenght") }
-
-        match stream[0] {
-            Open(_) => {
-                if let Some(close_idx) = Self::find_close_idx(&stream, 0) {
-                    if close_idx == len-1 {
-                        return Self::from(&stream[1..len-1])
-                    }
-                }
-            }
-            _ => {}
-        }
-
-        let mut parse_op = ParseOperator::new();
-        let mut skip: usize = 0;
+    /// Stelle des ganzen Teilausdrucks, Fehler bei der Auswertung zeigen
+    /// somit z.B.: auf `2 / 0` statt nur auf `/`.
+    pub fn span(&self) -> StartEnd {
+        self.span
+    }
 
-        for (idx, token) in stream.iter().enumerate() {
-            if idx < skip { continue }
-            match token {
-                Open(_) => {
-                    skip = Self::find_close_idx(&stream, idx).unwrap();
-                    continue
-                }
-                Op((op, _)) => {
-                    if parse_op.is_none() {
-                        parse_op.set(idx, *op)
-                    } else {
-                        match op {
-                            Operator::Plus |
-                            Operator::Minus => parse_op.set(idx, *op),
-                            Operator::Multiply |
-                            Operator::Divide => {
-                                match parse_op.kind() {
-                                    Operator::Multiply |
-                                    Operator::Divide => parse_op.set(idx, *op),
-                                    _ => {}
-                                }
-                            }
-                        }
-                    }
-                }
-                _ => {}
-            }
-        }
+    pub fn left(&self) -> &ParseTree {
+        &self.left
+    }
 
-        let operator = parse_op.kind();
-        let left = ParseTree::from(&stream[0..parse_op.idx()])?;
-        let right = ParseTree::from(&stream[parse_op.idx()+1..])?;
+    pub fn right(&self) -> &ParseTree {
+        &self.right
+    }
 
-        Ok(Self {
+    pub(super) fn new(operator: Operator, range: StartEnd, span: StartEnd, left: ParseTree, right: ParseTree) -> Self {
+        Self {
             operator,
+            range,
+            span,
             left: Box::new(left),
             right: Box::new(right),
-        })
+        }
     }
     
-    pub fn evaluate(&self) -> Result<f64, String> {
+    /// Zerlegt den Knoten in Operator, Stelle, Teilausdruck, links und rechts.
+    pub(super) fn into_parts(mut self) -> (Operator, StartEnd, StartEnd, ParseTree, ParseTree) {
+        let left = mem::replace(&mut *self.left, placeholder());
+        let right = mem::replace(&mut *self.right, placeholder());
+        (self.operator, self.range, self.span, left, right)
+    }
+
+    /// Kette der Knoten entlang der linken Operanden, z.B.: `1 + 2 - 3 × 4`
+    /// ergibt `1` und die Knoten `+ 2` und `- 3 × 4` in der Reihenfolge der
+    /// Auswertung, der letzte ist dieser Knoten. Eine lange Summe wird somit
+    /// ohne Rekursion durchlaufen.
+    pub fn chain(&self) -> (&ParseTree, Vec<&ParseNode>) {
+        let mut nodes = vec![self];
+        let mut first = self.left();
+        while let ParseTree::Node(node) = first {
+            nodes.push(node);
+            first = node.left();
+        }
+        nodes.reverse();
+        (first, nodes)
+    }
+
+    /// Wendet den Operator auf den Wert `left` des linken Operanden an.
+    pub fn evaluate(&self, left: Value, settings: &Settings, vars: &[f64], trace: &mut Trace) -> Result<Value, ErrorStruct> {
         use Operator::*;
         
-        let left = self.left.evaluate()?;
-        let right = self.right.evaluate()?;
+        let right = self.right.evaluate_bound(settings, vars, trace)?;
         
-        let number = match self.operator {
-            Plus => left + right,
-            Minus => left - right,
-            Multiply => left * right,
-            Divide => {
-                if right == 0f64 {
-                    return Err("Teilen durch Null nicht möglich".to_string());
+        // `a + b%` means `a*(1+b/100)`, calculated as `a + a*b/100` to keep `100 + 10%` exact
+        if let ParseTree::Postfix(PostfixOperator::Percent | PostfixOperator::Permille, _, _) = *self.right {
+            if let Value::Number(right) = right {
+                let res = match self.operator {
+                    Plus => Some(left.mul(&Value::Number(right)).and_then(|part| left.add(&part))),
+                    Minus => Some(left.mul(&Value::Number(right)).and_then(|part| left.sub(&part))),
+                    _ => None
+                };
+                if let Some(res) = res {
+                    return res.map_err(|msg| ErrorStruct::new(self.span(), msg))
+                }
+            }
+        }
+
+        // `10 € + 5 $` is calculated in the currency of the left operand if rates are loaded
+        let right = match (&left, &right, self.operator) {
+            (Value::Money(l), Value::Money(r), op) if settings.rates.is_some() && (matches!(op, Plus | Minus | Divide) || op.is_comparison()) => {
+                match r.convert(l.currency, settings.rates.as_ref()) {
+                    Ok(r) => Value::Money(r),
+                    Err(msg) => return Err(ErrorStruct::new(self.span(), msg))
                 }
-                left / right
             }
+            _ => right
+        };
+
+        let res = match self.operator {
+            Plus |
+            Compound => left.add(&right),
+            Minus => left.sub(&right),
+            Multiply |
+            ImplicitMultiply => left.mul(&right),
+            Divide => left.div(&right),
+            Convert => left.convert(&right, settings.rates.as_ref()),
+            Less |
+            Greater |
+            LessEqual |
+            GreaterEqual |
+            Equal |
+            NotEqual => Self::evaluate_comparison(self.operator, &left, &right),
+            And |
+            Or => Self::evaluate_logical(self.operator, &left, &right),
+            Modulo |
+            IntegerDivide |
+            BitAnd |
+            BitOr |
+            BitXor |
+            ShiftLeft |
+            ShiftRight => Self::evaluate_integer(self.operator, &left, &right),
+            Power => Self::evaluate_power(&left, &right),
         };
         
-        Ok(number)
+        res.map_err(|msg| ErrorStruct::new(self.span(), msg))
+    }
+
+    fn evaluate_comparison(operator: Operator, left: &Value, right: &Value) -> Result<Value, String> {
+        use Operator::*;
+
+        if let (Value::Bool(_), _) | (_, Value::Bool(_)) = (left, right) {
+            if !matches!(operator, Equal | NotEqual) {
+                return Err("Wahrheitswerte nur mit = und ≠ vergleichbar".to_string())
+            }
+        }
+        let ordering = left.compare(right)?;
+        let res = match operator {
+            Less => ordering == Ordering::Less,
+            Greater => ordering == Ordering::Greater,
+            LessEqual => ordering != Ordering::Greater,
+            GreaterEqual => ordering != Ordering::Less,
+            Equal => ordering == Ordering::Equal,
+            NotEqual => ordering != Ordering::Equal,
+            _ => unreachable!()
+        };
+        Ok(Value::Bool(res))
+    }
+
+    fn evaluate_logical(operator: Operator, left: &Value, right: &Value) -> Result<Value, String> {
+        let name = match operator {
+            Operator::And => "und",
+            _ => "oder",
+        };
+        let left = left.boolean(name)?;
+        let right = right.boolean(name)?;
+        let res = match operator {
+            Operator::And => left && right,
+            _ => left || right,
+        };
+        Ok(Value::Bool(res))
+    }
+
+    fn evaluate_integer(operator: Operator, left: &Value, right: &Value) -> Result<Value, String> {
+        use Operator::*;
+
+        let name = match operator {
+            Modulo => "Modulo",
+            IntegerDivide => "Ganzzahldivision",
+            _ => "Bitoperation",
+        };
+        let left = left.number(name)?;
+        let right = right.number(name)?;
+        let res = match operator {
+            Modulo => integer::modulo(left, right)?,
+            IntegerDivide => integer::divide(left, right)?,
+            _ => integer::bitwise(operator, left, right)?,
+        };
+        Ok(Value::Number(res))
+    }
+    
+    fn evaluate_power(left: &Value, right: &Value) -> Result<Value, String> {
+        let base = left.number("Potenz")?;
+        let exponent = right.number("Potenz")?;
+        Ok(Value::Number(base.powf(exponent)))
     }
     
     pub fn print(&self) -> String {
+        let (first, nodes) = self.chain();
+        let mut out = "(".repeat(nodes.len());
+        out.push_str(first.print().as_str());
+        for node in nodes {
+            out.push_str(node.symbol());
+            out.push_str(node.right.print().as_str());
+            out.push(')');
+        }
+        out
+    }
+
+    fn symbol(&self) -> &'static str {
         use Operator::*;
-        
-        let left = self.left.print();
-        let right = self.right.print();
-        
-        let op = match self.operator {
-            Plus => '+',
-            Minus => '-',
-            Multiply => char::from_u32(0x00d7).unwrap(),
-            Divide => char::from_u32(0x00f7).unwrap(),
-        };
-        
-        format!("({}{}{})", left, op, right)
+
+        match self.operator {
+            Plus => "+",
+            Minus => "-",
+            Multiply |
+            ImplicitMultiply => "\u{00d7}",
+            Divide => "\u{00f7}",
+            Modulo => " mod ",
+            IntegerDivide => " div ",
+            BitAnd => "&",
+            BitOr => "|",
+            BitXor => " xor ",
+            ShiftLeft => "<<",
+            ShiftRight => ">>",
+            Convert => " in ",
+            Compound => " ",
+            Less => "<",
+            Greater => ">",
+            LessEqual => "≤",
+            GreaterEqual => "≥",
+            Equal => "=",
+            NotEqual => "≠",
+            And => " und ",
+            Or => " oder ",
+            Power => "^",
+        }
+    }
+}
+
+/// Ersetzt einen Teilbaum welcher herausgenommen oder gleich freigegeben wird.
+fn placeholder() -> ParseTree {
+    ParseTree::Integer(0, StartEnd::from(0, 0))
+}
+
+/// Kopiert die Kette der linken Operanden ohne Rekursion, siehe [`ParseNode::chain`].
+impl Clone for ParseNode {
+    fn clone(&self) -> Self {
+        let (first, nodes) = self.chain();
+        let mut left = first.clone();
+        let last = nodes.len() -1;
+        for node in &nodes[..last] {
+            let node = Self::new(node.operator, node.range, node.span, left, node.right().clone());
+            left = ParseTree::Node(Box::new(node));
+        }
+        Self::new(self.operator, self.range, self.span, left, self.right().clone())
+    }
+}
+
+/// Gibt die Kette der linken Operanden ohne Rekursion frei, eine lange
+/// Summe würde sonst den Stack überlaufen lassen.
+impl Drop for ParseNode {
+    fn drop(&mut self) {
+        let mut left = mem::replace(&mut *self.left, placeholder());
+        while let ParseTree::Node(mut node) = left {
+            left = mem::replace(&mut *node.left, placeholder());
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,349 @@
+//! Baut den [`ParseTree`] in einem Durchgang über die Token nach Bindungsstärke
+//! der Operatoren auf. Zu jeder Klammer wird vorher einmal die passende
+//! schließende Klammer gesucht, somit ist der Aufwand linear zur Länge der
+//! Eingabe, auch bei tausenden Summanden.
+
+use super::super::Error;
+use super::super::ParseToken;
+use super::super::types::{Operator, PrefixOperator, PostfixOperator, Function, StartEnd};
+use super::node::ParseNode;
+use super::tree::ParseTree;
+
+/// Teilbaum mit der Anzahl seiner Ebenen.
+type Parsed = (ParseTree, usize);
+
+pub fn parse(stream: &[ParseToken], limit: usize) -> Result<ParseTree, Error<'static>> {
+    if stream.is_empty() {
+        return Ok(ParseTree::Integer(0, StartEnd::from(0, 0)))
+    }
+    let mut parser = Parser::new(stream, limit)?;
+    let (tree, _) = parser.expression(0, 0)?;
+    match parser.peek() {
+        None => Ok(tree),
+        Some(_) => Err(Error::ParseTree("Operator erwartet".to_string()))
+    }
+}
+
+struct Parser<'a> {
+    stream: &'a [ParseToken],
+    /// Index der passenden schließenden Klammer zu jeder öffnenden Klammer.
+    close: Vec<usize>,
+    /// Ein `nicht` vor einem Vergleich ist ein logisches `nicht`, siehe [`Parser::logical_not`].
+    logical: Vec<bool>,
+    idx: usize,
+    limit: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(stream: &'a [ParseToken], limit: usize) -> Result<Self, Error<'static>> {
+        use ParseToken::*;
+
+        let mut close = vec![0; stream.len()];
+        let mut open = Vec::new();
+        for (idx, token) in stream.iter().enumerate() {
+            match token {
+                Open(_) => open.push(idx),
+                Close(_) => {
+                    match open.pop() {
+                        Some(open) => close[open] = idx,
+                        None => return Err(Error::ParseTree("Öffnende Klammer fehlt".to_string()))
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !open.is_empty() {
+            return Err(Error::ParseTree("Schließende Klammer fehlt".to_string()))
+        }
+
+        Ok(Self {
+            stream,
+            close,
+            logical: Self::logical_not(stream),
+            idx: 0,
+            limit,
+        })
+    }
+
+    /// Ein `nicht` vor einem Vergleich ist ein logisches `nicht` und bindet
+    /// schwächer als der Vergleich, `nicht 3 > 5` ist `nicht (3 > 5)`. Vor
+    /// einer Zahl bindet es als bitweises `nicht` am stärksten. Ein Vergleich
+    /// zählt bis zum nächsten `und` oder `oder` in derselben Klammer, daher
+    /// werden die Token einmal von rechts nach links durchlaufen.
+    fn logical_not(stream: &[ParseToken]) -> Vec<bool> {
+        use ParseToken::*;
+
+        let mut logical = vec![false; stream.len()];
+        // Ob seit dem nächsten `und` oder `oder` ein Vergleich folgt, pro Klammer
+        let mut comparison = vec![false];
+        for (idx, token) in stream.iter().enumerate().rev() {
+            let last = comparison.len() -1;
+            match token {
+                Close(_) => comparison.push(false),
+                Open(_) if last > 0 => {
+                    comparison.pop();
+                }
+                Op((Operator::And | Operator::Or, _)) |
+                Separator(_) |
+                Then(_) |
+                Else(_) => comparison[last] = false,
+                Op((op, _)) if op.is_comparison() => comparison[last] = true,
+                Prefix((PrefixOperator::Not, _)) => logical[idx] = comparison[last],
+                _ => {}
+            }
+        }
+        logical
+    }
+
+    fn peek(&self) -> Option<&'a ParseToken> {
+        self.stream.get(self.idx)
+    }
+
+    /// Anfang des Tokens `idx` bis Ende des zuletzt gelesenen Tokens.
+    fn span(&self, idx: usize) -> StartEnd {
+        StartEnd::from(self.stream[idx].range().start, self.stream[self.idx -1].range().end)
+    }
+
+    /// `depth` ist die Ebene des Teilbaums, ab `limit` Ebenen ergibt dies
+    /// [`Error::TooDeep`] statt eines Überlaufs des Stacks.
+    fn check(&self, depth: usize) -> Result<(), Error<'static>> {
+        match depth >= self.limit {
+            true => Err(Error::TooDeep(self.limit)),
+            false => Ok(())
+        }
+    }
+
+    /// Anzahl der Ebenen eines Knotens über Teilbäumen mit `height` Ebenen.
+    fn height(&self, height: usize) -> Result<usize, Error<'static>> {
+        match height +1 > self.limit {
+            true => Err(Error::TooDeep(self.limit)),
+            false => Ok(height +1)
+        }
+    }
+
+    /// Liest Operatoren mit einer Bindungsstärke ab `min`, gleich starke
+    /// Operatoren binden von links nach rechts.
+    fn expression(&mut self, min: u8, depth: usize) -> Result<Parsed, Error<'static>> {
+        use ParseToken::*;
+
+        self.check(depth)?;
+        let start = self.idx;
+        // A parenthesized first operand is one level deeper if an operator follows
+        let nested = match self.peek() {
+            Some(Open(_)) => match self.stream.get(self.close[start] +1) {
+                Some(Op((op, _))) => op.precedence() >= min,
+                Some(Postfix(_) | Unit(_) | Currency(_)) => true,
+                _ => false
+            }
+            _ => false
+        };
+        let (mut left, mut height) = self.unary(depth + nested as usize)?;
+
+        while let Some(Op((op, range))) = self.peek() {
+            if op.precedence() < min { break }
+            self.idx += 1;
+            let right = self.expression(op.precedence() +1, depth +1)?;
+            (left, height) = self.node(*op, *range, start, (left, height), right)?;
+        }
+        Ok((left, height))
+    }
+
+    /// Knoten über `left` und `right`, sein Teilausdruck beginnt beim Token `start`.
+    /// Nur der rechte Operand ergibt eine weitere Ebene, die Kette der linken
+    /// Operanden wird ohne Rekursion durchlaufen, siehe [`ParseNode::chain`].
+    /// Eine lange Summe hat somit zwei Ebenen, `2^3^2` und `1 + (2 + 3)` drei.
+    fn node(&self, operator: Operator, range: StartEnd, start: usize, left: Parsed, right: Parsed) -> Result<Parsed, Error<'static>> {
+        let height = left.1.max(self.height(right.1)?);
+        let node = ParseNode::new(operator, range, self.span(start), left.0, right.0);
+        Ok((ParseTree::Node(Box::new(node)), height))
+    }
+
+    /// Operand mit Präfix- und Postfixoperatoren. Präfixoperatoren binden
+    /// schwächer als Postfixoperatoren und die Potenz, `-5!` ist `-(5!)`
+    /// und `-2^2` ist `-(2^2)`.
+    fn unary(&mut self, depth: usize) -> Result<Parsed, Error<'static>> {
+        use ParseToken::*;
+
+        self.check(depth)?;
+        match self.peek() {
+            Some(Op((Operator::Minus, _)) | Prefix(_)) => self.prefix(depth),
+            _ => self.postfix(depth)
+        }
+    }
+
+    fn prefix(&mut self, depth: usize) -> Result<Parsed, Error<'static>> {
+        use ParseToken::*;
+
+        let start = self.idx;
+        let op = match &self.stream[start] {
+            Op((_, minus)) => {
+                self.idx += 1;
+                // `-5` is a number unless a postfix operator follows
+                match self.peek() {
+                    Some(Number((n, range))) if !self.is_postfix(self.idx +1) && !self.is_power(self.idx +1) => {
+                        let n = match n.checked_neg() {
+                            Some(n) => n,
+                            None => return Err(Error::ParseTree("Zahl zu groß".to_string()))
+                        };
+                        self.idx += 1;
+                        return Ok((ParseTree::Integer(n, StartEnd::from(minus.start, range.end)), 1))
+                    }
+                    Some(Decimal((d, range))) if !self.is_postfix(self.idx +1) && !self.is_power(self.idx +1) => {
+                        self.idx += 1;
+                        return Ok((ParseTree::Decimal(d.neg(), StartEnd::from(minus.start, range.end)), 1))
+                    }
+                    _ => PrefixOperator::Minus
+                }
+            }
+            Prefix((op, _)) => {
+                self.idx += 1;
+                *op
+            }
+            _ => unreachable!()
+        };
+        let (tree, height) = match self.logical[start] {
+            // Up to the next `und` or `oder`
+            true => self.expression(Operator::And.precedence() +1, depth +1)?,
+            false => self.unary(depth +1)?,
+        };
+        Ok((ParseTree::Prefix(op, Box::new(tree), self.span(start)), self.height(height)?))
+    }
+
+    fn postfix(&mut self, depth: usize) -> Result<Parsed, Error<'static>> {
+        use ParseToken::*;
+
+        let start = self.idx;
+        let (mut tree, mut height) = self.primary(depth)?;
+        while let Some(token) = self.peek() {
+            let op = match token {
+                Postfix((op, _)) => *op,
+                Unit((idx, _)) => PostfixOperator::Unit(*idx),
+                Currency((idx, _)) => PostfixOperator::Currency(*idx),
+                _ => break
+            };
+            self.idx += 1;
+            height = self.height(height)?;
+            tree = ParseTree::Postfix(op, Box::new(tree), self.span(start));
+        }
+        // The exponent is read first, `2^3^2` is `2^(3^2)`
+        if let Some(Op((Operator::Power, range))) = self.peek() {
+            self.idx += 1;
+            let exponent = self.unary(depth +1)?;
+            return self.node(Operator::Power, *range, start, (tree, height), exponent)
+        }
+        Ok((tree, height))
+    }
+
+    fn is_postfix(&self, idx: usize) -> bool {
+        use ParseToken::*;
+
+        matches!(self.stream.get(idx), Some(Postfix(_) | Unit(_) | Currency(_)))
+    }
+
+    fn is_power(&self, idx: usize) -> bool {
+        matches!(self.stream.get(idx), Some(ParseToken::Op((Operator::Power, _))))
+    }
+
+    fn primary(&mut self, depth: usize) -> Result<Parsed, Error<'static>> {
+        use ParseToken::*;
+
+        let token = match self.peek() {
+            Some(token) => token,
+            None => return Err(Error::ParseTree("Operand erwartet".to_string()))
+        };
+        self.idx += 1;
+        match token {
+            Number((n, range)) => Ok((ParseTree::Integer(*n, *range), 1)),
+            Decimal((d, range)) => Ok((ParseTree::Decimal(*d, *range), 1)),
+            Unit((idx, range)) => Ok((ParseTree::Unit(*idx, *range), 1)),
+            Currency((idx, range)) => Ok((ParseTree::Currency(*idx, *range), 1)),
+            Date((date, range)) => Ok((ParseTree::Date(*date, *range), 1)),
+            Variable((variable, range)) => Ok((ParseTree::Variable(*variable, *range), 1)),
+            Open(_) => self.group(depth),
+            Function((function, _)) => self.function(*function, depth),
+            // The condition extends to the end, `1 + wenn a dann b sonst c + 2`
+            // has the else branch `c + 2`
+            If(_) => self.condition(depth),
+            _ => Err(Error::ParseTree("Operand erwartet".to_string()))
+        }
+    }
+
+    /// Klammern direkt ineinander werden ohne Rekursion übersprungen,
+    /// `((((1))))` ergibt somit keine weitere Ebene.
+    fn group(&mut self, depth: usize) -> Result<Parsed, Error<'static>> {
+        use ParseToken::*;
+
+        let mut open = self.idx -1;
+        let close = self.close[open];
+        while let (Open(_), true) = (&self.stream[open +1], self.close[open +1] == self.close[open] -1) {
+            open += 1;
+        }
+        self.idx = open +1;
+        let res = self.expression(0, depth)?;
+        match self.idx == self.close[open] {
+            true => self.idx = close +1,
+            false => return Err(Error::ParseTree("Operator erwartet".to_string()))
+        }
+        Ok(res)
+    }
+
+    /// Funktionsaufruf, die Argumente sind durch `;` getrennt. Das Argument
+    /// einer Funktion wie `sinus` kann ohne Klammern folgen, `sinus von x^2`
+    /// ist `sinus(x^2)`.
+    fn function(&mut self, function: Function, depth: usize) -> Result<Parsed, Error<'static>> {
+        use ParseToken::*;
+
+        let start = self.idx -1;
+        match self.peek() {
+            Some(Open(_)) => self.idx += 1,
+            Some(_) if function.is_unary() => {
+                let (arg, height) = self.postfix(depth +1)?;
+                return Ok((ParseTree::Function(function, vec![arg], self.span(start)), self.height(height)?))
+            }
+            _ => return Err(Error::ParseTree(format!("{} erwartet Klammern", function.name())))
+        }
+        let mut args = Vec::new();
+        let mut height = 0;
+        loop {
+            let (arg, arg_height) = self.expression(0, depth +1)?;
+            args.push(arg);
+            height = height.max(arg_height);
+            match self.peek() {
+                Some(Separator(_)) => self.idx += 1,
+                Some(Close(_)) => {
+                    self.idx += 1;
+                    break
+                }
+                _ => return Err(Error::ParseTree("Operator erwartet".to_string()))
+            }
+        }
+        match (function.is_unary(), args.len()) {
+            (true, 1) => {}
+            (true, _) => return Err(Error::ParseTree(format!("{} erwartet genau ein Argument", function.name()))),
+            (false, 0 | 1) => return Err(Error::ParseTree(format!("{} erwartet mindestens zwei Argumente", function.name()))),
+            (false, _) => {}
+        }
+        Ok((ParseTree::Function(function, args, self.span(start)), self.height(height)?))
+    }
+
+    /// Verschachtelte Bedingungen gehören zum jeweils nächsten `dann` und `sonst`.
+    fn condition(&mut self, depth: usize) -> Result<Parsed, Error<'static>> {
+        use ParseToken::*;
+
+        let start = self.idx -1;
+        let (condition, condition_height) = self.expression(0, depth +1)?;
+        match self.peek() {
+            Some(Then(_)) => self.idx += 1,
+            _ => return Err(Error::ParseTree("\"wenn\" ohne \"dann\"".to_string()))
+        }
+        let (then, then_height) = self.expression(0, depth +1)?;
+        match self.peek() {
+            Some(Else(_)) => self.idx += 1,
+            _ => return Err(Error::ParseTree("\"dann\" ohne \"sonst\"".to_string()))
+        }
+        let (otherwise, otherwise_height) = self.expression(0, depth +1)?;
+        let height = self.height(condition_height.max(then_height).max(otherwise_height))?;
+        let tree = ParseTree::Condition(Box::new(condition), Box::new(then), Box::new(otherwise), self.span(start));
+        Ok((tree, height))
+    }
+}
@@ -0,0 +1,299 @@
+//! Ausgabe eines [`ParseTree`] mit so wenig Klammern wie nötig, z.B.:
+//! `1 + 2 × 3` statt `(1+(2×3))`.
+
+use super::super::types::{Operator, PrefixOperator, PostfixOperator, Function};
+use super::super::value::Value;
+use super::super::settings::{Settings, Style, Base, Notation, Grouping};
+use super::super::format;
+use super::super::currency;
+use super::super::unit;
+use super::super::words;
+use super::trace::is_step;
+use super::node::ParseNode;
+use super::tree::ParseTree;
+
+/// Bindungsstärke von Präfix, Potenz, Postfix und einzelnen Werten beim
+/// Ausgeben, die übrigen binären Operatoren liegen darunter.
+pub(super) const PREFIX: u8 = 20;
+pub(super) const POWER: u8 = 21;
+pub(super) const POSTFIX: u8 = 22;
+pub(super) const ATOM: u8 = 23;
+
+/// Teil eines ausgegebenen Ausdrucks mit seiner Bindungsstärke.
+struct Part {
+    text: String,
+    precedence: u8,
+    negative: bool,
+}
+
+/// Gibt den Baum aus, die Teilbäume mit einer Nummer aus `values` werden
+/// durch ihren Wert ersetzt, siehe [`Trace`](super::trace::Trace).
+pub fn print(tree: &ParseTree, values: &[(usize, Value)], settings: &Settings, style: Style) -> String {
+    let mut settings = settings.clone();
+    settings.notation = Notation::Shortest;
+    let mut printer = Printer {
+        values,
+        next: 0,
+        settings: &settings,
+        style,
+    };
+    printer.tree(tree).text
+}
+
+struct Printer<'a> {
+    values: &'a [(usize, Value)],
+    next: usize,
+    settings: &'a Settings,
+    style: Style,
+}
+
+impl<'a> Printer<'a> {
+    /// Gibt die Teilbäume in derselben Reihenfolge wie die Auswertung aus,
+    /// somit stimmen die Nummern mit denen aus [`Trace::record`](super::trace::Trace::record) überein.
+    fn tree(&mut self, tree: &ParseTree) -> Part {
+        let part = self.children(tree);
+        match is_step(tree) {
+            true => self.step(part),
+            false => part
+        }
+    }
+
+    /// Ersetzt den Teilausdruck durch sein Zwischenergebnis, falls dieses
+    /// schon in `values` steht.
+    fn step(&mut self, part: Part) -> Part {
+        let id = self.next;
+        self.next += 1;
+        match self.values.binary_search_by_key(&id, |(id, _)| *id) {
+            Ok(idx) => self.value(&self.values[idx].1),
+            Err(_) => part
+        }
+    }
+
+    fn children(&mut self, tree: &ParseTree) -> Part {
+        match tree {
+            ParseTree::Number(n, _) => self.number(*n),
+            ParseTree::Integer(n, _) => self.integer(*n),
+            ParseTree::Decimal(d, _) => self.number(d.to_f64()),
+            ParseTree::Unit(idx, _) => atom(unit(*idx)),
+            ParseTree::Currency(idx, _) => atom(self.currency(*idx)),
+            ParseTree::Date(date, _) => atom(date.to_string()),
+            ParseTree::Variable(variable, _) => atom(variable.name.to_string()),
+            // The chain `1 + 2 - 3` is printed from the bottom without recursion,
+            // the topmost node is numbered in `tree`
+            ParseTree::Node(node) => {
+                let (first, nodes) = node.chain();
+                let mut part = self.tree(first);
+                let last = nodes.len() -1;
+                for (idx, node) in nodes.into_iter().enumerate() {
+                    let right = self.tree(node.right());
+                    part = self.node(node, part, right);
+                    if idx != last {
+                        part = self.step(part);
+                    }
+                }
+                part
+            }
+            ParseTree::Prefix(op, tree, _) => {
+                let tree = self.tree(tree);
+                let tree = self.wrap(tree, PREFIX, true);
+                let text = match (op, self.style) {
+                    (PrefixOperator::Minus, Style::Words) => format!("minus {}", tree),
+                    (PrefixOperator::Minus, _) => format!("-{}", tree),
+                    (PrefixOperator::Not, _) => format!("nicht {}", tree),
+                };
+                Part { text, precedence: PREFIX, negative: false }
+            }
+            ParseTree::Postfix(op, tree, _) => {
+                let tree = self.tree(tree);
+                let tree = self.wrap(tree, POSTFIX, true);
+                let text = match (op, self.style) {
+                    (PostfixOperator::Percent, Style::Words) => format!("{} prozent", tree),
+                    (PostfixOperator::Percent, _) => format!("{}%", tree),
+                    (PostfixOperator::Permille, Style::Unicode) => format!("{}‰", tree),
+                    (PostfixOperator::Permille, _) => format!("{} promille", tree),
+                    (PostfixOperator::Factorial, Style::Words) => format!("{} fakultät", tree),
+                    (PostfixOperator::Factorial, _) => format!("{}!", tree),
+                    (PostfixOperator::Unit(idx), _) => format!("{} {}", tree, unit(*idx)),
+                    (PostfixOperator::Currency(idx), _) => format!("{} {}", tree, self.currency(*idx)),
+                };
+                Part { text, precedence: POSTFIX, negative: false }
+            }
+            ParseTree::Function(Function::Between, args, _) => {
+                let args: Vec<String> = args.iter().map(|arg| self.tree(arg).text).collect();
+                Part { text: format!("zwischen {}", args.join(" und ")), precedence: 0, negative: false }
+            }
+            ParseTree::Function(function, args, _) => {
+                let args: Vec<String> = args.iter().map(|arg| self.tree(arg).text).collect();
+                atom(format!("{}({})", function.name(), args.join("; ")))
+            }
+            ParseTree::Condition(condition, then, otherwise, _) => {
+                let condition = self.tree(condition).text;
+                let then = self.tree(then).text;
+                let otherwise = self.tree(otherwise).text;
+                Part { text: format!("wenn {} dann {} sonst {}", condition, then, otherwise), precedence: 0, negative: false }
+            }
+        }
+    }
+
+    fn node(&self, node: &ParseNode, left: Part, right: Part) -> Part {
+        let operator = node.operator();
+        let precedence = precedence(operator);
+        // The power groups from the right, `(2^3)^2` needs parentheses
+        let power = operator == Operator::Power;
+        let mut text = self.wrap(left, precedence, power);
+        let right = self.wrap(right, precedence, !power);
+        if operator != Operator::Compound {
+            text.push(' ');
+            text.push_str(self::operator(operator, self.style));
+        }
+        text.push(' ');
+        text.push_str(right.as_str());
+        Part { text, precedence, negative: false }
+    }
+
+    /// Zahl aus der Eingabe, diese wird nicht gerundet. Ohne Gruppierung
+    /// mit Dezimalkomma, somit kann die Ausgabe wieder eingelesen werden.
+    fn number(&self, n: f64) -> Part {
+        let text = match (self.style, self.settings.base, self.settings.grouping) {
+            // Only whole numbers below a million can be read back as words
+            (Style::Words, Base::Decimal, _) if !n.is_finite() || (n.fract() == 0f64 && n.abs() < 1e6) => words::number(n),
+            (_, Base::Decimal, Grouping::None) => format::format_number(n, self.settings).replace('.', ","),
+            _ => format::format_number(n, self.settings),
+        };
+        // Digits among words keep the sign as a word, `minus 1,5`
+        let text = match (self.style, text.strip_prefix('-')) {
+            (Style::Words, Some(digits)) => format!("minus {}", digits),
+            _ => text
+        };
+        negative(text)
+    }
+
+    /// Ganze Zahlen jenseits von 2^53 stellt [`f64`] nicht exakt dar, sie
+    /// werden daher ohne Umweg über [`f64`] ausgegeben.
+    fn integer(&self, n: i64) -> Part {
+        match n.unsigned_abs() <= 1 << 53 {
+            true => self.number(n as f64),
+            false => negative(format::format_integer(n, self.settings)),
+        }
+    }
+
+    /// Zwischenergebnis, Zahlen werden auf 12 signifikante Stellen gerundet.
+    fn value(&self, value: &Value) -> Part {
+        match value {
+            Value::Number(n) => self.number(round(*n)),
+            Value::Date(date) => atom(date.to_string()),
+            Value::Money(money) if self.style == Style::Ascii => {
+                let text = format::format_money(money, self.settings);
+                let symbol = currency::display(money.currency);
+                negative(text.replace(symbol, currency::CURRENCIES[money.currency].code))
+            }
+            value => negative(format::format_value(value, self.settings)),
+        }
+    }
+
+    fn currency(&self, idx: usize) -> String {
+        match self.style {
+            Style::Ascii => currency::CURRENCIES[idx].code.to_string(),
+            _ => currency::display(idx).to_string(),
+        }
+    }
+
+    /// Klammert einen Teilausdruck falls er schwächer bindet als sein Operator,
+    /// bei gleicher Bindung nur auf der rechten Seite. Negative Werte werden
+    /// immer geklammert, z.B.: `1 + (-9)`.
+    fn wrap(&self, part: Part, precedence: u8, right: bool) -> String {
+        let needed = part.negative || part.precedence < precedence || (right && part.precedence == precedence);
+        match (needed, self.style) {
+            (false, _) => part.text,
+            (true, Style::Words) => format!("auf {} zu", part.text),
+            (true, _) => format!("({})", part.text),
+        }
+    }
+}
+
+/// Zeichen oder Wort eines binären Operators in der Darstellung `style`.
+pub(super) fn operator(operator: Operator, style: Style) -> &'static str {
+    use Operator::*;
+
+    match (operator, style) {
+        (Plus, Style::Words) => "plus",
+        (Plus, _) => "+",
+        (Minus, Style::Words) => "minus",
+        (Minus, _) => "-",
+        (Multiply | ImplicitMultiply, Style::Ascii) => "*",
+        (Multiply | ImplicitMultiply, Style::Unicode) => "\u{00d7}",
+        (Multiply | ImplicitMultiply, Style::Words) => "mal",
+        (Divide, Style::Ascii) => "/",
+        (Divide, Style::Unicode) => "\u{00f7}",
+        (Divide, Style::Words) => "durch",
+        (Modulo, _) => "mod",
+        (IntegerDivide, _) => "div",
+        (BitAnd, _) => "&",
+        (BitOr, _) => "|",
+        (BitXor, _) => "xor",
+        (ShiftLeft, _) => "<<",
+        (ShiftRight, _) => ">>",
+        (Convert, _) => "in",
+        (Less, Style::Words) => "kleiner als",
+        (Less, _) => "<",
+        (Greater, Style::Words) => "größer als",
+        (Greater, _) => ">",
+        (LessEqual, Style::Ascii) => "<=",
+        (LessEqual, Style::Unicode) => "≤",
+        (LessEqual, Style::Words) => "kleiner gleich",
+        (GreaterEqual, Style::Ascii) => ">=",
+        (GreaterEqual, Style::Unicode) => "≥",
+        (GreaterEqual, Style::Words) => "größer gleich",
+        (Equal, Style::Words) => "gleich",
+        (Equal, _) => "=",
+        (NotEqual, Style::Unicode) => "≠",
+        (NotEqual, _) => "ungleich",
+        (And, _) => "und",
+        (Or, _) => "oder",
+        (Compound, _) => "",
+        (Power, Style::Words) => "hoch",
+        (Power, _) => "^",
+    }
+}
+
+/// Bindungsstärke eines binären Operators beim Ausgeben, die implizite
+/// Multiplikation wird als `×` ausgegeben und bindet somit wie dieses.
+/// Die Potenz bindet stärker als ein Präfix, `-x^2` ist `-(x^2)`.
+pub(super) fn precedence(operator: Operator) -> u8 {
+    match operator {
+        Operator::ImplicitMultiply => Operator::Multiply.precedence() +1,
+        Operator::Power => POWER,
+        op => op.precedence() +1,
+    }
+}
+
+/// Symbol der Einheit, falls dieses nicht eingelesen werden kann der
+/// erste Name, z.B.: `Woche` statt `Wo`.
+fn unit(idx: usize) -> String {
+    let def = &unit::UNITS[idx];
+    if unit::find(def.symbol.to_lowercase().as_str()) == Some(idx) {
+        return def.symbol.to_string()
+    }
+    let mut chars = def.names[0].chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => def.symbol.to_string()
+    }
+}
+
+/// Rundet auf 12 signifikante Stellen, somit wird aus `55.00000000000001` wieder `55`.
+fn round(n: f64) -> f64 {
+    match format!("{:.11e}", n).parse() {
+        Ok(rounded) => rounded,
+        Err(_) => n
+    }
+}
+
+fn atom(text: String) -> Part {
+    Part { text, precedence: ATOM, negative: false }
+}
+
+fn negative(text: String) -> Part {
+    let negative = text.starts_with('-') || text.starts_with("minus");
+    Part { text, precedence: ATOM, negative }
+}
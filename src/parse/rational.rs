@@ -0,0 +1,103 @@
+//! Brüche für die Vereinfachung, z.B.: `6 ÷ 4` ergibt `3 ÷ 2`. Zähler und
+//! Nenner sind gekürzt und der Nenner ist positiv, ein Überlauf ergibt `None`.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    pub fn new(num: i64, den: i64) -> Option<Self> {
+        Self::reduce(num as i128, den as i128)
+    }
+
+    pub fn integer(n: i64) -> Self {
+        Self { num: n, den: 1 }
+    }
+
+    /// Zahlen mit höchstens neun Stellen hinter dem Komma, z.B.: `12,5`.
+    pub fn from_f64(n: f64) -> Option<Self> {
+        if !n.is_finite() {
+            return None
+        }
+        let mut den = 1i64;
+        for _ in 0..=9 {
+            let scaled = n * den as f64;
+            if scaled.fract() == 0f64 && scaled.abs() <= 9_007_199_254_740_992f64 {
+                return Self::new(scaled as i64, den)
+            }
+            den *= 10;
+        }
+        None
+    }
+
+    pub fn num(&self) -> i64 {
+        self.num
+    }
+
+    pub fn den(&self) -> i64 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.num < 0
+    }
+
+    pub fn abs(&self) -> Option<Self> {
+        Some(Self { num: self.num.checked_abs()?, den: self.den })
+    }
+
+    pub fn neg(&self) -> Option<Self> {
+        Some(Self { num: self.num.checked_neg()?, den: self.den })
+    }
+
+    pub fn add(&self, other: &Self) -> Option<Self> {
+        let num = self.num as i128 * other.den as i128 + other.num as i128 * self.den as i128;
+        Self::reduce(num, self.den as i128 * other.den as i128)
+    }
+
+    pub fn sub(&self, other: &Self) -> Option<Self> {
+        self.add(&other.neg()?)
+    }
+
+    pub fn mul(&self, other: &Self) -> Option<Self> {
+        Self::reduce(self.num as i128 * other.num as i128, self.den as i128 * other.den as i128)
+    }
+
+    /// Teilen durch Null ergibt `None`.
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        Self::reduce(self.num as i128 * other.den as i128, self.den as i128 * other.num as i128)
+    }
+
+    fn reduce(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None
+        }
+        let gcd = gcd(num.unsigned_abs(), den.unsigned_abs()) as i128;
+        let (num, den) = match den < 0 {
+            true => (-num / gcd, -den / gcd),
+            false => (num / gcd, den / gcd),
+        };
+        Some(Self {
+            num: i64::try_from(num).ok()?,
+            den: i64::try_from(den).ok()?,
+        })
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
@@ -0,0 +1,499 @@
+//! Symbolische Vereinfachung, z.B.: `2x + 3x - x × 1` ergibt `4 × x`. Der
+//! Ausdruck wird dafür ausmultipliziert und als Summe von Produkten mit
+//! einem Bruch als Faktor dargestellt. Was sich so nicht darstellen lässt,
+//! z.B.: ein Vergleich oder eine Funktion, wird zu einem eigenen Faktor
+//! dessen Teilausdrücke vereinfacht sind.
+
+use std::cmp::Ordering;
+
+use super::super::types::{Operator, PrefixOperator, PostfixOperator, StartEnd};
+use super::super::value::Value;
+use super::super::settings::Settings;
+use super::node::ParseNode;
+use super::rational::Rational;
+use super::tree::ParseTree;
+
+/// Mehr Summanden entstehen beim Ausmultiplizieren nicht, das Produkt
+/// bleibt sonst stehen.
+const MAX_TERMS: usize = 64;
+
+/// Höchster ganzzahliger Exponent einer Potenz die ausmultipliziert wird.
+const MAX_EXPONENT: u32 = 32;
+
+/// Lässt sich das Ergebnis nicht darstellen, z.B.: bei einem Überlauf des
+/// Bruchs, bleibt der Baum unverändert.
+pub fn simplify(tree: &ParseTree, settings: &Settings) -> ParseTree {
+    Polynomial::from(tree, settings).to_tree(tree.span()).unwrap_or_else(|| tree.clone())
+}
+
+/// Variable oder nicht weiter zerlegbarer Teilausdruck, `key` ist dessen
+/// Ausgabe und dient dem Vergleich.
+#[derive(Clone)]
+pub(super) struct Factor {
+    key: String,
+    tree: ParseTree,
+}
+
+/// Produkt aus einem Bruch und Faktoren mit ganzzahligem Exponenten, die
+/// Faktoren sind nach `key` sortiert und kein Exponent ist Null.
+#[derive(Clone)]
+pub(super) struct Term {
+    coefficient: Rational,
+    factors: Vec<(Factor, i32)>,
+}
+
+impl Term {
+    fn constant(coefficient: Rational) -> Self {
+        Self {
+            coefficient,
+            factors: Vec::new(),
+        }
+    }
+
+    pub fn coefficient(&self) -> Rational {
+        self.coefficient
+    }
+
+    /// Exponent von `key`, `0` wenn der Faktor nicht vorkommt.
+    pub fn exponent(&self, key: &str) -> i32 {
+        match self.factors.iter().find(|(factor, _)| factor.key == key) {
+            Some((_, exponent)) => *exponent,
+            None => 0
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.factors.is_empty()
+    }
+
+    /// Ob `key` der einzige Faktor ist, z.B.: bei `3 × x ^ 2` für `x`.
+    pub fn is_power_of(&self, key: &str) -> bool {
+        self.factors.iter().all(|(factor, _)| factor.key == key)
+    }
+
+    fn degree(&self) -> i32 {
+        self.factors.iter().map(|(_, exponent)| exponent).sum()
+    }
+
+    fn same_factors(&self, other: &Term) -> bool {
+        self.factors.len() == other.factors.len() &&
+            self.factors.iter().zip(&other.factors).all(|((a, x), (b, y))| a.key == b.key && x == y)
+    }
+
+    fn mul(&self, other: &Term) -> Option<Term> {
+        let coefficient = self.coefficient.mul(&other.coefficient)?;
+        let mut factors: Vec<(Factor, i32)> = Vec::with_capacity(self.factors.len() + other.factors.len());
+        let mut left = self.factors.iter().peekable();
+        let mut right = other.factors.iter().peekable();
+        loop {
+            let next = match (left.peek(), right.peek()) {
+                (Some((a, x)), Some((b, y))) => {
+                    match a.key.cmp(&b.key) {
+                        Ordering::Less => left.next().cloned(),
+                        Ordering::Greater => right.next().cloned(),
+                        Ordering::Equal => {
+                            let exponent = x.checked_add(*y)?;
+                            right.next();
+                            left.next().map(|(factor, _)| (factor.clone(), exponent))
+                        }
+                    }
+                }
+                (Some(_), None) => left.next().cloned(),
+                (None, Some(_)) => right.next().cloned(),
+                (None, None) => break
+            };
+            if let Some((factor, exponent)) = next {
+                if exponent != 0 {
+                    factors.push((factor, exponent));
+                }
+            }
+        }
+        Some(Term {
+            coefficient,
+            factors,
+        })
+    }
+
+    /// Kehrwert, Teilen durch Null ergibt `None`.
+    fn inverse(&self) -> Option<Term> {
+        let factors = self.factors.iter()
+            .map(|(factor, exponent)| (factor.clone(), -exponent))
+            .collect();
+        Some(Term {
+            coefficient: Rational::ONE.div(&self.coefficient)?,
+            factors,
+        })
+    }
+
+    /// Absteigend nach Grad, dann nach den Faktoren, Konstanten zuletzt.
+    fn order(&self, other: &Term) -> Ordering {
+        self.is_constant().cmp(&other.is_constant())
+            .then_with(|| other.degree().cmp(&self.degree()))
+            .then_with(|| {
+                let left = self.factors.iter().map(|(factor, exponent)| (factor.key.as_str(), -exponent));
+                let right = other.factors.iter().map(|(factor, exponent)| (factor.key.as_str(), -exponent));
+                left.cmp(right)
+            })
+    }
+}
+
+/// Summe von [`Term`], gleichartige Summanden sind zusammengefasst und
+/// keiner hat den Bruch Null.
+#[derive(Clone)]
+pub(super) struct Polynomial {
+    terms: Vec<Term>,
+}
+
+impl Polynomial {
+    pub fn from(tree: &ParseTree, settings: &Settings) -> Self {
+        expand(tree, settings)
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    fn constant(n: Rational) -> Self {
+        let terms = match n.is_zero() {
+            true => Vec::new(),
+            false => vec![Term::constant(n)],
+        };
+        Self { terms }
+    }
+
+    fn factor(tree: ParseTree) -> Self {
+        let factor = Factor {
+            key: tree.print(),
+            tree,
+        };
+        Self {
+            terms: vec![Term {
+                coefficient: Rational::ONE,
+                factors: vec![(factor, 1)],
+            }],
+        }
+    }
+
+    /// Fügt einen Summanden hinzu und fasst ihn mit einem gleichartigen zusammen.
+    fn push(&mut self, term: Term) -> Option<()> {
+        match self.terms.iter().position(|t| t.same_factors(&term)) {
+            Some(idx) => {
+                let coefficient = self.terms[idx].coefficient.add(&term.coefficient)?;
+                match coefficient.is_zero() {
+                    true => { self.terms.remove(idx); }
+                    false => self.terms[idx].coefficient = coefficient,
+                }
+            }
+            None if !term.coefficient.is_zero() => self.terms.push(term),
+            None => {}
+        }
+        Some(())
+    }
+
+    fn add(&self, other: &Self) -> Option<Self> {
+        let mut res = self.clone();
+        for term in &other.terms {
+            res.push(term.clone())?;
+        }
+        Some(res)
+    }
+
+    fn neg(&self) -> Option<Self> {
+        let mut res = self.clone();
+        for term in &mut res.terms {
+            term.coefficient = term.coefficient.neg()?;
+        }
+        Some(res)
+    }
+
+    fn sub(&self, other: &Self) -> Option<Self> {
+        self.add(&other.neg()?)
+    }
+
+    fn mul(&self, other: &Self) -> Option<Self> {
+        if let Some(res) = self.cancel(other).or_else(|| other.cancel(self)) {
+            return Some(res)
+        }
+        if self.terms.len() * other.terms.len() > MAX_TERMS {
+            return None
+        }
+        let mut res = Self { terms: Vec::new() };
+        for a in &self.terms {
+            for b in &other.terms {
+                res.push(a.mul(b)?)?;
+            }
+        }
+        Some(res)
+    }
+
+    /// Kürzt eine Summe gegen denselben Faktor im Nenner statt auszumultiplizieren,
+    /// z.B.: `x ÷ (x + 1) × (x + 1)`.
+    fn cancel(&self, other: &Self) -> Option<Self> {
+        if self.terms.len() != 1 || other.terms.len() < 2 {
+            return None
+        }
+        let tree = other.to_tree(StartEnd::from(0, 0))?;
+        if self.terms[0].exponent(tree.print().as_str()) >= 0 {
+            return None
+        }
+        self.mul(&Self::factor(tree))
+    }
+
+    /// Durch ein Produkt wird jeder Summand geteilt. Ist der Zähler ein
+    /// Vielfaches einer Summe wird gekürzt, z.B.: `(2x + 2) ÷ (x + 1)`, sonst
+    /// wird die Summe bei einem Produkt im Zähler zum Faktor im Nenner.
+    fn div(&self, other: &Self, span: StartEnd) -> Option<Self> {
+        match other.terms.len() {
+            0 => None,
+            1 => {
+                let inverse = other.terms[0].inverse()?;
+                self.mul(&Self { terms: vec![inverse] })
+            }
+            _ => {
+                if let Some(ratio) = self.ratio(other) {
+                    return Some(Self::constant(ratio))
+                }
+                if self.terms.len() != 1 {
+                    return None
+                }
+                let mut inverse = Self::factor(other.to_tree(span)?);
+                inverse.terms[0].factors[0].1 = -1;
+                self.mul(&inverse)
+            }
+        }
+    }
+
+    /// Potenz mit einem ganzzahligen Exponenten, z.B.: `(x + 1)^2`. Ein
+    /// negativer Exponent ergibt den Kehrwert.
+    fn pow(&self, other: &Self, span: StartEnd) -> Option<Self> {
+        let exponent = match other.terms.as_slice() {
+            [] => 0,
+            [term] if term.is_constant() && term.coefficient.den() == 1 => term.coefficient.num(),
+            _ => return None
+        };
+        if exponent.unsigned_abs() > MAX_EXPONENT as u64 {
+            return None
+        }
+        let mut res = Self::constant(Rational::ONE);
+        for _ in 0..exponent.unsigned_abs() {
+            res = res.mul(self)?;
+        }
+        match exponent < 0 {
+            true => Self::constant(Rational::ONE).div(&res, span),
+            false => Some(res),
+        }
+    }
+
+    /// Bruch `r` mit `self = r × other`, falls es diesen gibt.
+    fn ratio(&self, other: &Self) -> Option<Rational> {
+        if self.terms.len() != other.terms.len() {
+            return None
+        }
+        let mut ratio = None;
+        for term in &other.terms {
+            let matching = self.terms.iter().find(|t| t.same_factors(term))?;
+            let r = matching.coefficient.div(&term.coefficient)?;
+            match ratio {
+                Some(ratio) if ratio != r => return None,
+                _ => ratio = Some(r),
+            }
+        }
+        ratio
+    }
+
+    /// Baut den Ausdruck in Normalform, `span` erhalten alle neuen Knoten.
+    /// Ein Koeffizient `-2^63` hat keinen Betrag und ergibt `None`.
+    pub fn to_tree(&self, span: StartEnd) -> Option<ParseTree> {
+        let mut terms: Vec<&Term> = self.terms.iter().collect();
+        terms.sort_by(|a, b| a.order(b));
+
+        let mut res: Option<ParseTree> = None;
+        for term in terms {
+            let negative = term.coefficient.is_negative();
+            res = match res {
+                None => Some(term_tree(term, negative, span)?),
+                Some(left) => {
+                    let operator = match negative {
+                        true => Operator::Minus,
+                        false => Operator::Plus,
+                    };
+                    let right = term_tree(term, false, span)?;
+                    Some(node(operator, left, right, span))
+                }
+            };
+        }
+        Some(res.unwrap_or(ParseTree::Integer(0, span)))
+    }
+}
+
+fn expand(tree: &ParseTree, settings: &Settings) -> Polynomial {
+    match tree {
+        ParseTree::Integer(n, _) => Polynomial::constant(Rational::integer(*n)),
+        ParseTree::Number(n, _) => {
+            match Rational::from_f64(*n) {
+                Some(n) => Polynomial::constant(n),
+                None => Polynomial::factor(tree.clone())
+            }
+        }
+        ParseTree::Decimal(d, _) => {
+            match Rational::from_f64(d.to_f64()) {
+                Some(n) => Polynomial::constant(n),
+                None => Polynomial::factor(tree.clone())
+            }
+        }
+        ParseTree::Variable(_, _) => Polynomial::factor(tree.clone()),
+        // The chain `2x + 3x - x` is expanded from the bottom without recursion
+        ParseTree::Node(node) => {
+            let (first, nodes) = node.chain();
+            let mut left = expand(first, settings);
+            for node in nodes {
+                left = expand_node(node, left, settings);
+            }
+            left
+        }
+        ParseTree::Prefix(PrefixOperator::Minus, inner, span) => {
+            let polynomial = expand(inner, settings);
+            match polynomial.neg() {
+                Some(res) => res,
+                None => {
+                    let inner = polynomial.to_tree(*span).unwrap_or_else(|| (**inner).clone());
+                    opaque(ParseTree::Prefix(PrefixOperator::Minus, Box::new(inner), *span), settings)
+                }
+            }
+        }
+        tree => opaque(children(tree, settings), settings)
+    }
+}
+
+/// Wendet den Operator auf `left`, den schon ausmultiplizierten linken
+/// Operanden, an.
+fn expand_node(node: &ParseNode, left: Polynomial, settings: &Settings) -> Polynomial {
+    // `a + b%` has its own meaning and is kept
+    if let ParseTree::Postfix(PostfixOperator::Percent | PostfixOperator::Permille, _, _) = node.right() {
+        let left = left.to_tree(node.left().span()).unwrap_or_else(|| node.left().clone());
+        let right = simplify(node.right(), settings);
+        let tree = ParseTree::Node(Box::new(ParseNode::new(node.operator(), node.range(), node.span(), left, right)));
+        return opaque(tree, settings)
+    }
+    let right = expand(node.right(), settings);
+    let res = match node.operator() {
+        Operator::Plus => left.add(&right),
+        Operator::Minus => left.sub(&right),
+        Operator::Multiply |
+        Operator::ImplicitMultiply => left.mul(&right),
+        Operator::Divide => left.div(&right, node.right().span()),
+        Operator::Power => left.pow(&right, node.left().span()),
+        _ => None
+    };
+    match res {
+        Some(res) => res,
+        None => {
+            let left = left.to_tree(node.left().span()).unwrap_or_else(|| node.left().clone());
+            let right = right.to_tree(node.right().span()).unwrap_or_else(|| node.right().clone());
+            let tree = ParseTree::Node(Box::new(ParseNode::new(node.operator(), node.range(), node.span(), left, right)));
+            opaque(tree, settings)
+        }
+    }
+}
+
+/// Vereinfacht die direkten Teilausdrücke.
+fn children(tree: &ParseTree, settings: &Settings) -> ParseTree {
+    match tree {
+        ParseTree::Node(node) => {
+            let left = simplify(node.left(), settings);
+            let right = simplify(node.right(), settings);
+            ParseTree::Node(Box::new(ParseNode::new(node.operator(), node.range(), node.span(), left, right)))
+        }
+        ParseTree::Prefix(op, inner, span) => ParseTree::Prefix(*op, Box::new(simplify(inner, settings)), *span),
+        ParseTree::Postfix(op, inner, span) => ParseTree::Postfix(*op, Box::new(simplify(inner, settings)), *span),
+        ParseTree::Function(function, args, span) => {
+            let args = args.iter().map(|arg| simplify(arg, settings)).collect();
+            ParseTree::Function(*function, args, *span)
+        }
+        ParseTree::Condition(condition, then, otherwise, span) => {
+            let condition = simplify(condition, settings);
+            let then = simplify(then, settings);
+            let otherwise = simplify(otherwise, settings);
+            ParseTree::Condition(Box::new(condition), Box::new(then), Box::new(otherwise), *span)
+        }
+        tree => tree.clone()
+    }
+}
+
+/// Ein Teilausdruck ohne Variablen dessen Wert ein Bruch ist wird zur
+/// Konstanten, alles andere zu einem eigenen Faktor, z.B.: `ln(2)`.
+fn opaque(tree: ParseTree, settings: &Settings) -> Polynomial {
+    // `10%` keeps its meaning in `x + 10%`
+    let percent = matches!(tree, ParseTree::Postfix(PostfixOperator::Percent | PostfixOperator::Permille, _, _));
+    if !percent && !has_variables(&tree) {
+        if let Ok(Value::Number(n)) = tree.evaluate(settings) {
+            if let Some(n) = Rational::from_f64(n) {
+                return Polynomial::constant(n)
+            }
+        }
+    }
+    Polynomial::factor(tree)
+}
+
+pub(super) fn has_variables(tree: &ParseTree) -> bool {
+    match tree {
+        ParseTree::Variable(_, _) => true,
+        ParseTree::Node(node) => {
+            let (first, nodes) = node.chain();
+            has_variables(first) || nodes.iter().any(|node| has_variables(node.right()))
+        }
+        ParseTree::Prefix(_, tree, _) |
+        ParseTree::Postfix(_, tree, _) => has_variables(tree),
+        ParseTree::Function(_, args, _) => args.iter().any(has_variables),
+        ParseTree::Condition(condition, then, otherwise, _) => {
+            has_variables(condition) || has_variables(then) || has_variables(otherwise)
+        }
+        _ => false
+    }
+}
+
+/// Summand ohne Vorzeichen, mit `negative` erhält der erste Faktor ein Minus,
+/// z.B.: `-3 × x ^ 2 ÷ 2`. Variablen stehen vor den übrigen Faktoren.
+fn term_tree(term: &Term, negative: bool, span: StartEnd) -> Option<ParseTree> {
+    let coefficient = term.coefficient.abs()?;
+    let mut numerator = Vec::new();
+    let mut denominator = Vec::new();
+    if coefficient.num() != 1 || term.factors.iter().all(|(_, exponent)| *exponent < 0) {
+        numerator.push(ParseTree::Integer(coefficient.num(), span));
+    }
+    if coefficient.den() != 1 {
+        denominator.push(ParseTree::Integer(coefficient.den(), span));
+    }
+    let mut factors: Vec<&(Factor, i32)> = term.factors.iter().collect();
+    factors.sort_by_key(|(factor, _)| !matches!(factor.tree, ParseTree::Variable(_, _)));
+    for (factor, exponent) in factors {
+        let list = match *exponent > 0 {
+            true => &mut numerator,
+            false => &mut denominator,
+        };
+        let factor = match exponent.unsigned_abs() {
+            1 => factor.tree.clone(),
+            n => node(Operator::Power, factor.tree.clone(), ParseTree::Integer(n as i64, span), span),
+        };
+        list.push(factor);
+    }
+    if negative {
+        let first = numerator.remove(0);
+        numerator.insert(0, ParseTree::Prefix(PrefixOperator::Minus, Box::new(first), span));
+    }
+    let numerator = product(numerator, span);
+    match denominator.is_empty() {
+        true => Some(numerator),
+        false => Some(node(Operator::Divide, numerator, product(denominator, span), span)),
+    }
+}
+
+fn product(factors: Vec<ParseTree>, span: StartEnd) -> ParseTree {
+    let mut factors = factors.into_iter();
+    let first = factors.next().unwrap_or(ParseTree::Integer(1, span));
+    factors.fold(first, |left, right| node(Operator::Multiply, left, right, span))
+}
+
+fn node(operator: Operator, left: ParseTree, right: ParseTree, span: StartEnd) -> ParseTree {
+    ParseTree::Node(Box::new(ParseNode::new(operator, span, span, left, right)))
+}
@@ -0,0 +1,280 @@
+//! Lösen einer Gleichung mit einer Unbekannten, z.B.: `3x + 5 = 20`. Die
+//! Gleichung wird zu `3x + 5 - 20 = 0` umgeformt und wie bei der
+//! [`Vereinfachung`](super::simplify) ausmultipliziert. Lineare Gleichungen
+//! werden exakt mit Brüchen gelöst, quadratische mit der Lösungsformel und
+//! alle anderen numerisch durch Intervallhalbierung.
+
+use super::super::types::{Operator, PrefixOperator, Function, StartEnd, Variable};
+use super::super::error::ErrorStruct;
+use super::super::settings::Settings;
+use super::node::ParseNode;
+use super::rational::Rational;
+use super::simplify::Polynomial;
+use super::tree::ParseTree;
+
+/// Anzahl der Teilintervalle in denen nach einem Vorzeichenwechsel gesucht wird.
+const SAMPLES: usize = 1000;
+
+/// Größte Abweichung von Null an einer numerischen Lösung, an einer
+/// Polstelle wie bei `1 ÷ x` wechselt das Vorzeichen ebenfalls.
+const TOLERANCE: f64 = 1e-6;
+
+/// Lösungen einer Gleichung, siehe [`ParseTree::solve`].
+#[derive(Clone)]
+pub enum Solutions {
+    /// z.B.: `x + 1 = x`
+    None,
+    /// Jede Zahl ist eine Lösung, z.B.: `2x = x + x`
+    All,
+    /// Exakte Lösungen aufsteigend mit ihrem Wert, z.B.: `5 ÷ 3` oder `1 + wurzel(2)`
+    Exact(Variable, Vec<(ParseTree, f64)>),
+    /// Nullstellen mit Vorzeichenwechsel im gesuchten Bereich
+    Numeric(Variable, Vec<f64>),
+}
+
+pub fn solve(tree: &ParseTree, interval: (f64, f64), settings: &Settings) -> Result<Solutions, ErrorStruct> {
+    let span = tree.span();
+    let difference = match tree {
+        ParseTree::Node(node) if node.operator() == Operator::Equal => {
+            binary(Operator::Minus, node.left().clone(), node.right().clone(), span)
+        }
+        ParseTree::Node(node) if node.operator().is_comparison() => {
+            return Err(ErrorStruct::new(node.range(), "Nur Gleichungen mit \"=\" lösbar".to_string()))
+        }
+        // `x^2 - 4` is `x^2 - 4 = 0`
+        tree => tree.clone()
+    };
+
+    let mut variables = Vec::new();
+    collect(&difference, &mut variables);
+    let variable = match variables.as_slice() {
+        [] => return constant(&difference, settings),
+        [variable] => *variable,
+        _ => {
+            let names: Vec<&str> = variables.iter().map(|variable| variable.name).collect();
+            return Err(ErrorStruct::new(span, format!("Gleichung mit mehreren Unbekannten {} nicht lösbar", names.join(", "))))
+        }
+    };
+
+    let polynomial = Polynomial::from(&difference, settings);
+    let roots = match coefficients(&polynomial, variable.name).as_deref() {
+        Some([]) => return Ok(Solutions::All),
+        Some([_]) => return Ok(Solutions::None),
+        Some([b, a]) => linear(*a, *b, settings, span),
+        Some([c, b, a]) => quadratic(*a, *b, *c, settings, span),
+        _ => None
+    };
+    match roots {
+        Some(roots) if roots.is_empty() => Ok(Solutions::None),
+        Some(roots) => {
+            let mut res = Vec::with_capacity(roots.len());
+            for root in roots {
+                let value = root.evaluate(settings)?.number("Lösung").map_err(|msg| ErrorStruct::new(span, msg))?;
+                res.push((root, value));
+            }
+            Ok(Solutions::Exact(variable, res))
+        }
+        // Higher degree, not a polynomial or too large for exact fractions
+        None => numeric(&difference, variable, interval, settings).map(|roots| Solutions::Numeric(variable, roots))
+    }
+}
+
+/// Gleichung ohne Unbekannte, z.B.: `2 + 2 = 4`.
+fn constant(difference: &ParseTree, settings: &Settings) -> Result<Solutions, ErrorStruct> {
+    let value = difference.evaluate(settings)?;
+    match value.number("Gleichung") {
+        Ok(0f64) => Ok(Solutions::All),
+        Ok(_) => Ok(Solutions::None),
+        Err(msg) => Err(ErrorStruct::new(difference.span(), msg))
+    }
+}
+
+/// Koeffizienten nach aufsteigendem Grad falls jeder Summand nur eine
+/// Potenz von `name` enthält, z.B.: `[-15, 3]` für `3 × x - 15`.
+fn coefficients(polynomial: &Polynomial, name: &str) -> Option<Vec<Rational>> {
+    let mut res = Vec::new();
+    for term in polynomial.terms() {
+        let degree = usize::try_from(term.exponent(name)).ok()?;
+        if !term.is_power_of(name) {
+            return None
+        }
+        if res.len() <= degree {
+            res.resize(degree +1, Rational::ZERO);
+        }
+        res[degree] = term.coefficient();
+    }
+    Some(res)
+}
+
+/// `a × x + b = 0` ergibt `x = -b ÷ a`.
+fn linear(a: Rational, b: Rational, settings: &Settings, span: StartEnd) -> Option<Vec<ParseTree>> {
+    let root = b.neg()?.div(&a)?;
+    Some(vec![rational(root, settings, span)])
+}
+
+/// `a × x^2 + b × x + c = 0` ergibt `x = (-b ± wurzel(b^2 - 4ac)) ÷ 2a`,
+/// Quadratfaktoren werden dabei aus der Wurzel gezogen.
+fn quadratic(a: Rational, b: Rational, c: Rational, settings: &Settings, span: StartEnd) -> Option<Vec<ParseTree>> {
+    let two_a = a.mul(&Rational::integer(2))?;
+    let vertex = b.neg()?.div(&two_a)?;
+    let discriminant = b.mul(&b)?.sub(&a.mul(&c)?.mul(&Rational::integer(4))?)?;
+    if discriminant.is_negative() {
+        return Some(Vec::new())
+    }
+    if discriminant.is_zero() {
+        return Some(vec![rational(vertex, settings, span)])
+    }
+
+    // wurzel(p ÷ q) is wurzel(p × q) ÷ q, that is k × wurzel(m) ÷ q
+    let (k, m) = square_part(discriminant.num() as i128 * discriminant.den() as i128);
+    let distance = Rational::new(i64::try_from(k).ok()?, discriminant.den())?.div(&two_a)?.abs()?;
+    if m == 1 {
+        let roots = [vertex.sub(&distance)?, vertex.add(&distance)?];
+        return Some(roots.into_iter().map(|root| rational(root, settings, span)).collect())
+    }
+    let sqrt = ParseTree::Function(Function::Sqrt, vec![ParseTree::Integer(i64::try_from(m).ok()?, span)], span);
+    let distance = binary(Operator::Multiply, rational(distance, settings, span), sqrt, span).simplify(settings);
+    let roots = match vertex.is_zero() {
+        true => vec![ParseTree::Prefix(PrefixOperator::Minus, Box::new(distance.clone()), span).simplify(settings), distance],
+        false => {
+            let vertex = rational(vertex, settings, span);
+            vec![
+                binary(Operator::Minus, vertex.clone(), distance.clone(), span),
+                binary(Operator::Plus, vertex, distance, span),
+            ]
+        }
+    };
+    Some(roots)
+}
+
+/// Zerlegt `n` in `k^2 × m`, kleine Quadratfaktoren werden durch Probieren
+/// gefunden, z.B.: `(2, 3)` für `12`.
+fn square_part(n: i128) -> (i128, i128) {
+    let (mut k, mut m) = (1, n);
+    let mut d: i128 = 2;
+    while d <= 1000 && d * d <= m {
+        while m % (d * d) == 0 {
+            m /= d * d;
+            k *= d;
+        }
+        d += 1;
+    }
+    let root = isqrt(m);
+    match root * root == m {
+        true => (k * root, 1),
+        false => (k, m),
+    }
+}
+
+fn isqrt(n: i128) -> i128 {
+    let mut root = (n as f64).sqrt() as i128;
+    while root * root > n {
+        root -= 1;
+    }
+    while (root +1) * (root +1) <= n {
+        root += 1;
+    }
+    root
+}
+
+/// Sucht in `SAMPLES` gleich großen Teilintervallen nach einem
+/// Vorzeichenwechsel und halbiert diese bis zur Genauigkeit von [`f64`].
+/// Stellen an denen die Gleichung keinen Wert hat werden übersprungen,
+/// z.B.: `ln(x)` für `x ≤ 0`.
+fn numeric(difference: &ParseTree, variable: Variable, interval: (f64, f64), settings: &Settings) -> Result<Vec<f64>, ErrorStruct> {
+    let mut vars = vec![0f64; variable.idx +1];
+    let mut f = |x: f64| -> Result<f64, ErrorStruct> {
+        vars[variable.idx] = x;
+        let value = difference.evaluate_with(settings, &vars)?;
+        value.number("Gleichung").map_err(|msg| ErrorStruct::new(difference.span(), msg))
+    };
+
+    let (start, end) = interval;
+    let step = (end - start) / SAMPLES as f64;
+    let mut samples = Vec::with_capacity(SAMPLES +1);
+    let mut error = None;
+    for i in 0..=SAMPLES {
+        let x = match i {
+            SAMPLES => end,
+            i => start + step * i as f64,
+        };
+        let y = match f(x) {
+            Ok(y) => y,
+            Err(e) => {
+                error.get_or_insert(e);
+                f64::NAN
+            }
+        };
+        samples.push((x, y));
+    }
+    if let (true, Some(e)) = (samples.iter().all(|(_, y)| y.is_nan()), error) {
+        return Err(e)
+    }
+
+    let mut roots = Vec::new();
+    for (idx, window) in samples.windows(2).enumerate() {
+        let ((x0, y0), (x1, y1)) = (window[0], window[1]);
+        if y0 == 0f64 {
+            roots.push(x0);
+        } else if y1 != 0f64 && y0.is_finite() && y1.is_finite() && y0.signum() != y1.signum() {
+            if let Some(x) = bisect(|x| f(x).ok(), (x0, y0), x1) {
+                roots.push(x);
+            }
+        }
+        if idx == SAMPLES -1 && y1 == 0f64 {
+            roots.push(x1);
+        }
+    }
+    Ok(roots)
+}
+
+fn bisect(mut f: impl FnMut(f64) -> Option<f64>, (mut low, mut y_low): (f64, f64), mut high: f64) -> Option<f64> {
+    loop {
+        let mid = low + (high - low) / 2f64;
+        if mid <= low || mid >= high {
+            break
+        }
+        let y = f(mid)?;
+        if y == 0f64 {
+            return Some(mid)
+        }
+        match y.signum() == y_low.signum() {
+            true => (low, y_low) = (mid, y),
+            false => high = mid,
+        }
+    }
+    match f(low) {
+        Some(y) if y.abs() <= TOLERANCE => Some(low),
+        _ => None
+    }
+}
+
+/// Variablen des Ausdrucks in der Reihenfolge ihres Vorkommens.
+fn collect(tree: &ParseTree, variables: &mut Vec<Variable>) {
+    match tree {
+        ParseTree::Variable(variable, _) if !variables.contains(variable) => variables.push(*variable),
+        ParseTree::Node(node) => {
+            let (first, nodes) = node.chain();
+            collect(first, variables);
+            nodes.iter().for_each(|node| collect(node.right(), variables));
+        }
+        ParseTree::Prefix(_, tree, _) |
+        ParseTree::Postfix(_, tree, _) => collect(tree, variables),
+        ParseTree::Function(_, args, _) => args.iter().for_each(|arg| collect(arg, variables)),
+        ParseTree::Condition(condition, then, otherwise, _) => {
+            collect(condition, variables);
+            collect(then, variables);
+            collect(otherwise, variables);
+        }
+        _ => {}
+    }
+}
+
+/// Bruch in Normalform, z.B.: `-1 ÷ 2`.
+fn rational(n: Rational, settings: &Settings, span: StartEnd) -> ParseTree {
+    binary(Operator::Divide, ParseTree::Integer(n.num(), span), ParseTree::Integer(n.den(), span), span).simplify(settings)
+}
+
+fn binary(operator: Operator, left: ParseTree, right: ParseTree, span: StartEnd) -> ParseTree {
+    ParseTree::Node(Box::new(ParseNode::new(operator, span, span, left, right)))
+}
@@ -0,0 +1,125 @@
+use super::super::types::PostfixOperator;
+use super::super::value::Value;
+use super::super::settings::{Settings, Style};
+use super::print;
+use super::tree::ParseTree;
+
+/// Zeichnet die Zwischenergebnisse einer Auswertung auf. Jeder Knoten
+/// welcher einen Wert berechnet erhält in der Reihenfolge der Auswertung
+/// eine Nummer, der nicht gewählte Zweig einer Bedingung wird übersprungen.
+pub struct Trace {
+    enabled: bool,
+    /// Zeichnet jeden Knoten auf, nicht nur die Rechenschritte.
+    all: bool,
+    next: usize,
+    values: Vec<(usize, Value)>,
+}
+
+impl Trace {
+    /// Keine Aufzeichnung, für die normale Auswertung.
+    pub fn none() -> Self {
+        Self {
+            enabled: false,
+            all: false,
+            next: 0,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            all: false,
+            next: 0,
+            values: Vec::new(),
+        }
+    }
+
+    /// Zeichnet die Werte aller Knoten in Postorder auf, siehe [`Trace::value`].
+    pub fn all() -> Self {
+        Self {
+            enabled: true,
+            all: true,
+            next: 0,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Zeichnet den Wert eines Knotens auf, mit `step` ist dieser ein
+    /// Rechenschritt, siehe [`is_step`].
+    pub fn record(&mut self, step: bool, value: &Value) {
+        if !self.enabled || !(self.all || step) {
+            return
+        }
+        self.values.push((self.next, value.clone()));
+        self.next += 1;
+    }
+
+    /// Überspringt einen nicht ausgewerteten Teilbaum.
+    pub fn skip(&mut self, tree: &ParseTree) {
+        if self.enabled {
+            self.next += count(tree, self.all);
+        }
+    }
+
+    /// Wert des Knotens mit der Nummer `id`, falls dieser berechnet wurde.
+    pub fn value(&self, id: usize) -> Option<&Value> {
+        match self.values.binary_search_by_key(&id, |(id, _)| *id) {
+            Ok(idx) => Some(&self.values[idx].1),
+            Err(_) => None
+        }
+    }
+
+    /// Gibt den Baum aus, die ersten `steps` Zwischenergebnisse ersetzen
+    /// dabei ihren Teilbaum, z.B.: `1 + (-18) ÷ 2` nach dem ersten Schritt.
+    pub fn print(&self, tree: &ParseTree, steps: usize, settings: &Settings, style: Style) -> String {
+        print::print(tree, &self.values[..steps], settings, style)
+    }
+}
+
+/// Knoten welche einen eigenen Schritt ergeben. Prozent, Einheit und
+/// Währung gehören zu ihrem Wert, `10%` bleibt somit in `50 + 10%` stehen.
+pub(super) fn is_step(tree: &ParseTree) -> bool {
+    match tree {
+        ParseTree::Number(_, _) |
+        ParseTree::Integer(_, _) |
+        ParseTree::Decimal(_, _) |
+        ParseTree::Unit(_, _) |
+        ParseTree::Currency(_, _) |
+        ParseTree::Date(_, _) |
+        ParseTree::Variable(_, _) => false,
+        ParseTree::Postfix(op, _, _) => matches!(op, PostfixOperator::Factorial),
+        ParseTree::Node(_) |
+        ParseTree::Prefix(_, _, _) |
+        ParseTree::Function(_, _, _) |
+        ParseTree::Condition(_, _, _, _) => true,
+    }
+}
+
+/// Anzahl der Schritte bzw. mit `all` der Knoten in einem Teilbaum.
+pub(super) fn count(tree: &ParseTree, all: bool) -> usize {
+    let children = match tree {
+        ParseTree::Number(_, _) |
+        ParseTree::Integer(_, _) |
+        ParseTree::Decimal(_, _) |
+        ParseTree::Unit(_, _) |
+        ParseTree::Currency(_, _) |
+        ParseTree::Date(_, _) |
+        ParseTree::Variable(_, _) => 0,
+        // Every node of the chain `1 + 2 - 3` counts, its left operands are not visited again
+        ParseTree::Node(node) => {
+            let (first, nodes) = node.chain();
+            let right: usize = nodes.iter().map(|node| count(node.right(), all)).sum();
+            return count(first, all) + right + nodes.len()
+        }
+        ParseTree::Prefix(_, tree, _) |
+        ParseTree::Postfix(_, tree, _) => count(tree, all),
+        ParseTree::Function(_, args, _) => args.iter().map(|arg| count(arg, all)).sum(),
+        ParseTree::Condition(condition, then, otherwise, _) => count(condition, all) + count(then, all) + count(otherwise, all),
+    };
+    children + (all || is_step(tree)) as usize
+}
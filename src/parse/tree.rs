@@ -1,80 +1,357 @@
 use super::super::Error;
 use super::super::ParseToken;
-use super::super::types::Operator;
+use super::super::error::ErrorStruct;
+use super::super::types::{PrefixOperator, PostfixOperator, Function, StartEnd, Variable};
+use super::super::unit;
+use super::super::value::{Value, Quantity, Money};
+use super::super::settings::{Settings, Style, Export, NonFinite, Limits};
+use super::super::currency;
+use super::super::date::Date;
+use super::super::decimal::Decimal;
+use super::integer;
 use super::node::ParseNode;
+use super::parser;
+use super::trace::{self, Trace};
+use super::print;
+use super::export;
+use super::exact;
+use super::fold;
+use super::simplify;
+use super::derive;
+use super::solve::{self, Solutions};
+use super::dump::{self, Dump};
 
+/// Jeder Knoten kennt die Stelle seines Teilausdrucks in der Eingabe, siehe
+/// [`ParseTree::span`] und [`ParseTree::source`].
+#[derive(Clone)]
 pub enum ParseTree {
-    Number(f64),
+    Number(f64, StartEnd),
+    /// Ganze Zahl aus der Eingabe, bleibt für [`ParseTree::evaluate_exact`]
+    /// exakt erhalten.
+    Integer(i64, StartEnd),
+    /// Dezimalzahl aus der Eingabe, bleibt für Geldbeträge exakt erhalten,
+    /// z.B.: `12,50` in `12,50 €`.
+    Decimal(Decimal, StartEnd),
+    /// Einheit ohne Zahl, z.B.: `Millimeter` in `3 m in Millimeter`
+    Unit(usize, StartEnd),
+    /// Währung ohne Betrag, z.B.: `€` in `100 $ in €`
+    Currency(usize, StartEnd),
+    Date(Date, StartEnd),
+    /// Variable, der Wert kommt aus der Belegung bei der Auswertung, siehe
+    /// [`ParseTree::evaluate_with`].
+    Variable(Variable, StartEnd),
     Node(Box<ParseNode>),
+    Prefix(PrefixOperator, Box<ParseTree>, StartEnd),
+    Postfix(PostfixOperator, Box<ParseTree>, StartEnd),
+    Function(Function, Vec<ParseTree>, StartEnd),
+    /// `wenn` Bedingung `dann` Wert `sonst` Wert
+    Condition(Box<ParseTree>, Box<ParseTree>, Box<ParseTree>, StartEnd),
 }
 
 impl ParseTree {
-    pub fn from(stream: &[ParseToken]) -> Result<Self, Error> {
-        use ParseToken::*;
-        
-        let len = stream.len();
-        if len == 0 { return Ok(Self::Number(0f64)) }
-        else {
-            match len {
-                1 => {
-                    match stream[0] {
-                        Number((n, _)) => return Ok(Self::Number(n as f64)),
-                        _ => return Err(Error::ParseTree("Expect nubmer variant".to_string()))
+    pub fn from(stream: &[ParseToken]) -> Result<Self, Error<'static>> {
+        Self::limited(stream, Limits::new().depth)
+    }
+
+    /// Erstellt den Baum in linearer Zeit, ab `limit` Ebenen ergibt dies
+    /// [`Error::TooDeep`] statt eines Überlaufs des Stacks.
+    pub fn limited(stream: &[ParseToken], limit: usize) -> Result<Self, Error<'static>> {
+        parser::parse(stream, limit)
+    }
+
+    pub fn evaluate(&self, settings: &Settings) -> Result<Value, ErrorStruct> {
+        self.evaluate_bound(settings, &[], &mut Trace::none())
+    }
+
+    /// Wertet den Baum mit den Werten der Variablen aus, `vars[idx]` ist der
+    /// Wert von [`Variable::idx`].
+    pub fn evaluate_with(&self, settings: &Settings, vars: &[f64]) -> Result<Value, ErrorStruct> {
+        self.evaluate_bound(settings, vars, &mut Trace::none())
+    }
+
+    /// Wertet den Baum aus und übergibt jedes Zwischenergebnis an `trace`.
+    pub fn evaluate_traced(&self, settings: &Settings, trace: &mut Trace) -> Result<Value, ErrorStruct> {
+        self.evaluate_bound(settings, &[], trace)
+    }
+
+    pub(super) fn evaluate_bound(&self, settings: &Settings, vars: &[f64], trace: &mut Trace) -> Result<Value, ErrorStruct> {
+        let value = self.evaluate_tree(settings, vars, trace)?;
+        Self::bound(value, self.span(), trace::is_step(self), settings, trace)
+    }
+
+    /// Prüft das Ergebnis des Teilausdrucks an der Stelle `span` und zeichnet
+    /// es mit `step` als Rechenschritt auf.
+    fn bound(value: Value, span: StartEnd, step: bool, settings: &Settings, trace: &mut Trace) -> Result<Value, ErrorStruct> {
+        if let (Some(n), NonFinite::Error) = (value.non_finite(), settings.non_finite) {
+            let msg = match n.is_nan() {
+                true => "Ergebnis nicht definiert",
+                false => "Ergebnis unendlich",
+            };
+            return Err(ErrorStruct::new(span, msg.to_string()))
+        }
+        trace.record(step, &value);
+        Ok(value)
+    }
+
+    fn evaluate_tree(&self, settings: &Settings, vars: &[f64], trace: &mut Trace) -> Result<Value, ErrorStruct> {
+        match self {
+            Self::Number(n, _) => Ok(Value::Number(*n)),
+            Self::Integer(n, _) => Ok(Value::Number(*n as f64)),
+            Self::Decimal(d, _) => Ok(Value::Number(d.to_f64())),
+            Self::Unit(idx, _) => Ok(Value::Quantity(Quantity::from(1f64, *idx))),
+            Self::Currency(idx, _) => Ok(Value::Money(Money::from(1f64, *idx).unwrap())),
+            Self::Date(date, _) => Ok(Value::Date(*date)),
+            Self::Variable(variable, range) => {
+                match vars.get(variable.idx) {
+                    Some(n) => Ok(Value::Number(*n)),
+                    None => Err(ErrorStruct::new(*range, format!("Variable {} hat keinen Wert", variable.name)))
+                }
+            }
+            // The chain `1 + 2 - 3` is evaluated from the bottom without recursion,
+            // the topmost node is checked and recorded in `evaluate_bound`
+            Self::Node(node) => {
+                let (first, nodes) = node.chain();
+                let mut value = first.evaluate_bound(settings, vars, trace)?;
+                let last = nodes.len() -1;
+                for (idx, node) in nodes.into_iter().enumerate() {
+                    value = node.evaluate(value, settings, vars, trace)?;
+                    if idx != last {
+                        value = Self::bound(value, node.span(), true, settings, trace)?;
                     }
                 }
-                2 => {
-                    match stream[0] {
-                        Op((Operator::Minus, _)) => {}
-                        _ => return Err(Error::ParseTree("Invalid prefix operator".to_string()))
+                Ok(value)
+            }
+            Self::Prefix(op, tree, range) => {
+                let value = tree.evaluate_bound(settings, vars, trace)?;
+                let res = match op {
+                    PrefixOperator::Minus => value.neg(),
+                    PrefixOperator::Not => {
+                        match value {
+                            Value::Bool(b) => Ok(Value::Bool(!b)),
+                            _ => value.number("Bitoperation").and_then(integer::not).map(Value::Number),
+                        }
                     }
-                    match stream[1] {
-                        Number((n, _)) => return Ok(Self::Number(-n as f64)),
-                        _ => return Err(Error::ParseTree("Expect number variant".to_string()))
+                };
+                res.map_err(|msg| ErrorStruct::new(*range, msg))
+            }
+            Self::Postfix(op, tree, range) => {
+                let value = tree.evaluate_bound(settings, vars, trace)?;
+                let res = match op {
+                    PostfixOperator::Percent => value.number("Prozent").map(|n| Value::Number(n / 100f64)),
+                    PostfixOperator::Permille => value.number("Promille").map(|n| Value::Number(n / 1000f64)),
+                    PostfixOperator::Factorial => value.number("Fakultät").and_then(integer::factorial).map(Value::Number),
+                    PostfixOperator::Unit(idx) => {
+                        match value {
+                            Value::Number(n) => Ok(Value::Quantity(Quantity::from(n, *idx))),
+                            _ => Err("Einheit hier nicht möglich".to_string())
+                        }
                     }
-                }
-                _ => {
-                    match stream[0] {
-                        Open(_) => {
-                            if let Some(close_idx) = ParseNode::find_close_idx(&stream, 0) {
-                                if close_idx == len -1 {
-                                    return Self::from(&stream[1..len-1])
-                                }
-                            }
+                    PostfixOperator::Currency(idx) => {
+                        // Literals keep all their digits instead of going through f64
+                        let amount = match tree.as_ref() {
+                            Self::Decimal(d, _) => Some(*d),
+                            Self::Integer(n, _) => Some(Decimal::new(*n as i128, 0)),
+                            _ => None
+                        };
+                        match (value, amount) {
+                            (Value::Number(_), Some(amount)) => Ok(Value::Money(Money { amount, currency: *idx })),
+                            (Value::Number(n), None) => Money::from(n, *idx).map(Value::Money),
+                            _ => Err("Währung hier nicht möglich".to_string())
                         }
-                        _ => {}
                     }
+                };
+                res.map_err(|msg| ErrorStruct::new(*range, msg))
+            }
+            // Only the selected branch is evaluated
+            Self::Condition(condition, then, otherwise, range) => {
+                let condition = condition.evaluate_bound(settings, vars, trace)?;
+                match condition.boolean("wenn") {
+                    Ok(true) => {
+                        let value = then.evaluate_bound(settings, vars, trace)?;
+                        trace.skip(otherwise);
+                        Ok(value)
+                    }
+                    Ok(false) => {
+                        trace.skip(then);
+                        otherwise.evaluate_bound(settings, vars, trace)
+                    }
+                    Err(msg) => Err(ErrorStruct::new(*range, msg))
                 }
             }
-        }
-        
-        match ParseNode::from(&stream) {
-            Ok(node) => return Ok(Self::Node(Box::new(node))),
-            Err(e) => return Err(e)
+            Self::Function(Function::Between, args, range) => {
+                if args.len() != 2 {
+                    return Err(ErrorStruct::new(*range, "zwischen erwartet genau zwei Argumente".to_string()))
+                }
+                let from = args[0].evaluate_bound(settings, vars, trace)?;
+                let to = args[1].evaluate_bound(settings, vars, trace)?;
+                to.sub(&from).map_err(|msg| ErrorStruct::new(*range, msg))
+            }
+            Self::Function(function, args, range) => {
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    let value = arg.evaluate_bound(settings, vars, trace)?;
+                    values.push(value.number(function.name()).map_err(|msg| ErrorStruct::new(*range, msg))?);
+                }
+                let res = match function {
+                    Function::Gcd => integer::gcd(&values),
+                    Function::Lcm => integer::lcm(&values),
+                    Function::Sin => Ok(values[0].sin()),
+                    Function::Cos => Ok(values[0].cos()),
+                    Function::Tan => Ok(values[0].tan()),
+                    Function::Exp => Ok(values[0].exp()),
+                    Function::Ln => Ok(values[0].ln()),
+                    Function::Sqrt => Ok(values[0].sqrt()),
+                    Function::Between => unreachable!(),
+                };
+                res.map(Value::Number).map_err(|msg| ErrorStruct::new(*range, msg))
+            }
         }
     }
+    
+    /// Anzahl der Knoten, also die höchste Anzahl an Rechenschritten.
+    pub fn size(&self) -> usize {
+        trace::count(self, true)
+    }
 
-    pub fn evaluate(&self) -> Result<f64, String> {
-        match self {
-            Self::Number(n) => Ok(*n),
-            Self::Node(node) => {
-                match node.evaluate() {
-                    Ok(n) => Ok(n),
-                    Err(e) => Err(e)
-                }
+    /// Rechnet exakt mit ganzen Zahlen, siehe [`Settings::exact`]. Gibt
+    /// `None` zurück wenn der Ausdruck nicht nur aus ganzen Zahlen besteht.
+    pub fn evaluate_exact(&self) -> Result<Option<i64>, ErrorStruct> {
+        exact::evaluate(self)
+    }
+
+    /// Ersetzt Teilausdrücke ohne Variablen durch ihren Wert, z.B.: `2 × 3 × x`
+    /// durch `6 × x`. Teilausdrücke mit einem Fehler bleiben erhalten.
+    pub fn fold(self, settings: &Settings) -> Self {
+        fold::fold(self, settings)
+    }
+
+    /// Fasst den Baum symbolisch zusammen, z.B.: `2x + 3x` zu `5 × x` und
+    /// `(6x + 4) ÷ 2` zu `3 × x + 2`. Die Ausgabe mit [`ParseTree::pretty`]
+    /// ist danach in Normalform, Summanden nach absteigendem Grad.
+    pub fn simplify(&self, settings: &Settings) -> Self {
+        simplify::simplify(self, settings)
+    }
+
+    /// Leitet den Baum nach `variable` ab und vereinfacht das Ergebnis, z.B.:
+    /// `x^2 × sin(x)` ergibt `x ^ 2 × cos(x) + 2 × x × sin(x)`. Einheiten,
+    /// Vergleiche und ganzzahlige Operatoren lassen sich nicht ableiten.
+    pub fn derive(&self, variable: Variable, settings: &Settings) -> Result<Self, ErrorStruct> {
+        derive::derive(self, variable).map(|tree| tree.simplify(settings))
+    }
+
+    /// Löst die Gleichung `links = rechts` nach ihrer einzigen Unbekannten,
+    /// ohne `=` ist die rechte Seite Null. Gleichungen höheren Grades oder
+    /// mit Funktionen werden numerisch im Bereich `interval` gelöst.
+    pub fn solve(&self, interval: (f64, f64), settings: &Settings) -> Result<Solutions, ErrorStruct> {
+        solve::solve(self, interval, settings)
+    }
+
+    /// Wertet den Baum aus und gibt jeden Rechenschritt aus, z.B.:
+    /// `1 + (-18) ÷ 2`, `1 + (-9)` und `-8`.
+    pub fn steps(&self, settings: &Settings, style: Style) -> Result<Vec<String>, ErrorStruct> {
+        let mut trace = Trace::new();
+        self.evaluate_traced(settings, &mut trace)?;
+
+        let mut steps: Vec<String> = Vec::with_capacity(trace.len() +1);
+        for count in 0..=trace.len() {
+            let step = trace.print(self, count, settings, style);
+            if steps.last() != Some(&step) {
+                steps.push(step);
             }
         }
+        Ok(steps)
     }
     
+    /// Gibt den Baum mit so wenig Klammern wie nötig aus, z.B.: `1 + 2 × 3`.
+    pub fn pretty(&self, style: Style) -> String {
+        print::print(self, &[], &Settings::new(), style)
+    }
+
+    /// Stelle des ganzen Teilausdrucks in der Eingabe, z.B.: `2 / 0` in
+    /// `1 + 2 / 0`. Klammern um den Teilausdruck gehören nicht dazu.
+    pub fn span(&self) -> StartEnd {
+        match self {
+            Self::Number(_, span) |
+            Self::Integer(_, span) |
+            Self::Decimal(_, span) |
+            Self::Unit(_, span) |
+            Self::Currency(_, span) |
+            Self::Date(_, span) |
+            Self::Variable(_, span) => *span,
+            Self::Node(node) => node.span(),
+            Self::Prefix(_, _, span) |
+            Self::Postfix(_, _, span) |
+            Self::Function(_, _, span) |
+            Self::Condition(_, _, _, span) => *span,
+        }
+    }
+
+    /// Text des Teilausdrucks in der Eingabe aus welcher der Baum entstanden ist.
+    pub fn source(&self, input: &str) -> String {
+        let span = self.span();
+        input.get(span.start..span.end).unwrap_or_default().to_string()
+    }
+
+    /// Gibt den Baum mit Stelle und Wert jedes Knotens aus, siehe [`Dump`].
+    pub fn dump(&self, input: &str, settings: &Settings, format: Dump) -> String {
+        dump::dump(self, input, settings, format)
+    }
+
+    /// Gibt den Baum als LaTeX oder MathML aus, z.B.: `\frac{1}{2}`.
+    pub fn export(&self, format: Export) -> String {
+        export::export(self, format)
+    }
+
     pub fn print(&self) -> String {
         match self {
-            Self::Number(n) => {
+            Self::Number(n, _) => {
                 if *n < 0f64 {
-                    return format!("(0{})", n)
+                    format!("(0{})", n)
+                } else {
+                    format!("{}", n)
+                }
+            }
+            Self::Integer(n, _) => {
+                if *n < 0 {
+                    format!("(0{})", n)
                 } else {
-                    return format!("{}", n)
+                    format!("{}", n)
                 }
             }
-            Self::Node(node) => return format!("{}", node.print()),
+            Self::Decimal(d, _) => {
+                if d.is_negative() {
+                    format!("(0{})", d)
+                } else {
+                    format!("{}", d)
+                }
+            }
+            Self::Unit(idx, _) => unit::UNITS[*idx].symbol.to_string(),
+            Self::Currency(idx, _) => currency::display(*idx).to_string(),
+            Self::Date(date, _) => date.to_string(),
+            Self::Variable(variable, _) => variable.name.to_string(),
+            Self::Node(node) => node.print(),
+            Self::Prefix(op, tree, _) => {
+                match op {
+                    PrefixOperator::Minus => format!("(0-{})", tree.print()),
+                    PrefixOperator::Not => format!("(nicht {})", tree.print()),
+                }
+            }
+            Self::Postfix(op, tree, _) => {
+                match op {
+                    PostfixOperator::Percent => format!("{}%", tree.print()),
+                    PostfixOperator::Permille => format!("{}‰", tree.print()),
+                    PostfixOperator::Factorial => format!("{}!", tree.print()),
+                    PostfixOperator::Unit(idx) => format!("{}{}", tree.print(), unit::UNITS[*idx].symbol),
+                    PostfixOperator::Currency(idx) => format!("{}{}", tree.print(), currency::display(*idx)),
+                }
+            }
+            Self::Function(function, args, _) => {
+                let args: Vec<String> = args.iter().map(|arg| arg.print()).collect();
+                format!("{}({})", function.name(), args.join(";"))
+            }
+            Self::Condition(condition, then, otherwise, _) => {
+                format!("(wenn {} dann {} sonst {})", condition.print(), then.print(), otherwise.print())
+            }
         }
     }
 }
\ No newline at end of file
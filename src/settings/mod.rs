@@ -0,0 +1,165 @@
+use super::currency::RateTable;
+
+/// Einstellungen welche das Verhalten von [`calculate_with`](crate::calculate_with)
+/// beeinflussen. Im Komandozeilenrechner können diese zur Laufzeit
+/// geändert werden.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// Zwei direkt aufeinander folgende Operanden werden multipliziert,
+    /// z.B.: `2(3+4)` oder `(1+2)(3+4)`.
+    pub implicit_multiplication: bool,
+    /// Ausdrücke aus ganzen Zahlen werden exakt mit 64 Bit berechnet, ein
+    /// Überlauf ergibt einen Fehler, z.B.: `9007199254740993` wird nicht auf
+    /// `9007199254740992` gerundet.
+    pub exact: bool,
+    /// Umgang mit Ergebnissen wie `unendlich` und `nicht definiert`.
+    pub non_finite: NonFinite,
+    /// Zahlensystem in dem das Ergebnis ausgegeben wird.
+    pub base: Base,
+    /// Darstellung von Dezimalzahlen in der Ausgabe.
+    pub notation: Notation,
+    /// Gruppierung der Ziffern und Dezimaltrennzeichen der Ausgabe.
+    pub grouping: Grouping,
+    /// Rundung von Geldbeträgen bei der Ausgabe.
+    pub rounding: Rounding,
+    /// Abweichende Anzahl an Stellen hinter dem Komma pro Währung.
+    pub currency_digits: Vec<(usize, u32)>,
+    /// Wechselkurse für die Umrechnung von Währungen, z.B.: `100 USD in EUR`.
+    pub rates: Option<RateTable>,
+    /// Schreibweise in welcher die Eingabe wiederholt wird.
+    pub style: Style,
+    /// Gibt zusätzlich jeden Rechenschritt aus, siehe [`Style`].
+    pub steps: Option<Style>,
+    /// Gibt die Eingabe zusätzlich als LaTeX oder MathML aus.
+    pub export: Option<Export>,
+    /// Grenzen für Eingaben aus fremden Quellen.
+    pub limits: Limits,
+}
+
+/// Grenzen für Eingaben aus fremden Quellen, z.B.: in einem Chat Bot. Jede
+/// überschrittene Grenze ergibt einen eigenen [`Error`](crate::error::Error).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Limits {
+    /// Anzahl der Zeichen der Eingabe.
+    pub input: usize,
+    /// Anzahl der Token nach der Zerlegung der Eingabe.
+    pub tokens: usize,
+    /// Anzahl der Ebenen des Baums, begrenzt die Rekursion beim Aufbau,
+    /// bei der Auswertung und bei der Ausgabe. Nur Klammern, Präfixe und
+    /// rechte Operanden ergeben eine Ebene, eine lange Summe hat zwei.
+    pub depth: usize,
+    /// Anzahl der Rechenschritte, also der ausgewerteten Knoten.
+    pub steps: usize,
+}
+
+impl Limits {
+    pub fn new() -> Self {
+        Self {
+            input: 100_000,
+            tokens: 50_000,
+            depth: 200,
+            steps: 100_000,
+        }
+    }
+}
+
+/// Umgang mit Zwischenergebnissen welche keine endliche Zahl sind, z.B.: bei
+/// einem Überlauf von [`f64`] oder `unendlich - unendlich`. Teilen durch Null
+/// ist immer ein Fehler.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NonFinite {
+    /// Fehler an der Stelle an welcher der Wert entsteht.
+    Error,
+    /// Ausgabe als `unendlich`, `-unendlich` oder `nicht definiert`.
+    Display,
+}
+
+/// Zahlensystem der Ausgabe, siehe [`format_number`](crate::format::format_number).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Base {
+    Decimal,
+    Hexadecimal,
+    Binary,
+    Octal,
+}
+
+/// Darstellung einer Dezimalzahl, siehe [`format_number`](crate::format::format_number).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Notation {
+    /// Kürzeste Darstellung welche beim Einlesen wieder dieselbe Zahl ergibt.
+    Shortest,
+    /// Anzahl der signifikanten Stellen.
+    Significant(usize),
+    /// Anzahl der Stellen hinter dem Komma.
+    Fixed(usize),
+    /// Wissenschaftliche Darstellung mit Anzahl der Stellen hinter dem Komma, z.B.: `1.23e4`.
+    Scientific(usize),
+    /// Technische Darstellung, der Exponent ist ein Vielfaches von drei, z.B.: `12.3e3`.
+    Engineering(usize),
+}
+
+/// Gruppierung der Ziffern vor dem Komma und Dezimaltrennzeichen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Grouping {
+    /// Keine Gruppierung, Dezimalpunkt: `1234567.89`
+    None,
+    /// Deutsche Schreibweise: `1.234.567,89`
+    German,
+    /// Internationale Schreibweise: `1,234,567.89`
+    International,
+}
+
+/// Rundung bei genau der Hälfte, z.B.: `0,125` auf zwei Stellen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Rounding {
+    /// Kaufmännisch, von Null weg: `0,13`
+    Commercial,
+    /// Zur geraden Ziffer: `0,12`
+    Bankers,
+}
+
+/// Schreibweise der Eingabe und der Rechenschritte.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Style {
+    /// Nur ASCII Zeichen: `2 * (-9) / 3`
+    Ascii,
+    /// Mit Zeichen: `2 × (-9) ÷ 3`
+    Unicode,
+    /// In Worten: `eins plus auf minus neun zu`
+    Words,
+}
+
+/// Format für die Weitergabe der Eingabe, z.B.: in einen Bericht.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Export {
+    /// `\frac{1}{2} \cdot 3`
+    Latex,
+    /// `<mfrac><mn>1</mn><mn>2</mn></mfrac>`
+    MathMl,
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self {
+            implicit_multiplication: false,
+            exact: false,
+            non_finite: NonFinite::Error,
+            base: Base::Decimal,
+            notation: Notation::Shortest,
+            grouping: Grouping::None,
+            rounding: Rounding::Commercial,
+            currency_digits: Vec::new(),
+            rates: None,
+            style: Style::Unicode,
+            steps: None,
+            export: None,
+            limits: Limits::new(),
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,106 @@
+//! Ausgabe der einzelnen Stufen der Zerlegung einer Eingabe für
+//! Fehlerberichte, siehe [`dump_tokens`].
+
+use super::lexer::Lexer;
+use super::parse::ParseStream;
+use super::word::WordTokenStream;
+use super::super::token::{ParseToken, InputToken};
+use super::super::types::StartEnd;
+use super::super::settings::Settings;
+use super::super::unit;
+use super::super::currency;
+
+/// Listet die Zeichenklassen der Eingabe, die Teile jedes Wortes und die
+/// daraus entstandenen [`ParseToken`] mit ihrer Stelle in der Eingabe.
+pub fn dump_tokens(input: &str, settings: &Settings) -> String {
+    let mut out = String::new();
+
+    out.push_str("Zeichen:\n");
+    for (range, class) in classes(input) {
+        let text = &input[range.start..range.end];
+        out.push_str(format!("  {:>3}..{:<3} {:<12} \"{}\"\n", range.start, range.end, class, text).as_str());
+    }
+
+    out.push_str("Wörter:\n");
+    for range in words(input) {
+        let word = input[range.start..range.end].to_lowercase();
+        let parts = match (unit::find(word.as_str()), currency::find(word.as_str()), WordTokenStream::from(word.as_str())) {
+            (Some(idx), _, _) => format!("Einheit {}", unit::UNITS[idx].symbol),
+            (None, Some(idx), _) => format!("Währung {}", currency::CURRENCIES[idx].code),
+            (None, None, Some(stream)) if stream.len() == 1 => format!("{:?}", stream[0]),
+            // Teilwörter mit ihrer Stelle in der Eingabe
+            (None, None, Some(stream)) if stream.len() > 1 => {
+                let parts: Vec<String> = stream.iter().enumerate().map(|(idx, token)| {
+                    let span = stream.span(idx);
+                    format!("{:?} {}..{}", token, range.start + span.start, range.start + span.end)
+                }).collect();
+                parts.join(", ")
+            }
+            _ => "unbekannt".to_string()
+        };
+        out.push_str(format!("  {:>3}..{:<3} {:<20} {}\n", range.start, range.end, word, parts).as_str());
+    }
+
+    out.push_str("Token:\n");
+    match ParseStream::from(input) {
+        Ok(mut stream) => {
+            if settings.implicit_multiplication {
+                stream.insert_implicit_multiplication();
+            }
+            for token in &stream[..] {
+                let (name, range) = describe(token);
+                let text = input.get(range.start..range.end).unwrap_or_default();
+                out.push_str(format!("  {:>3}..{:<3} {:<20} \"{}\"\n", range.start, range.end, name, text).as_str());
+            }
+        }
+        Err(e) => out.push_str(format!("{}\n", e).as_str())
+    }
+    out
+}
+
+/// Abschnitte aus dem [`Lexer`], aufeinander folgende Buchstaben, Ziffern
+/// und Leerzeichen sind bereits zusammengefasst.
+fn classes(input: &str) -> Vec<(StartEnd, &'static str)> {
+    use InputToken::*;
+
+    Lexer::new(input).map(|token| {
+        let class = match token {
+            Space(_) => "Leerzeichen",
+            Letters(_) => "Buchstaben",
+            Digits(_) => "Ziffern",
+            Symbol(_) => "Zeichen",
+            Whatever(_) => "unbekannt",
+        };
+        (token.range(), class)
+    }).collect()
+}
+
+fn words(input: &str) -> Vec<StartEnd> {
+    classes(input).into_iter()
+        .filter(|(_, class)| *class == "Buchstaben")
+        .map(|(range, _)| range)
+        .collect()
+}
+
+fn describe(token: &ParseToken) -> (String, StartEnd) {
+    use ParseToken::*;
+
+    match token {
+        Number((n, range)) => (format!("Number({})", n), *range),
+        Decimal((d, range)) => (format!("Decimal({})", d), *range),
+        Date((date, range)) => (format!("Date({})", date), *range),
+        Op((op, range)) => (format!("Op({:?})", op), *range),
+        Prefix((op, range)) => (format!("Prefix({:?})", op), *range),
+        Postfix((op, range)) => (format!("Postfix({:?})", op), *range),
+        Function((function, range)) => (format!("Function({})", function.name()), *range),
+        Unit((idx, range)) => (format!("Unit({})", unit::UNITS[*idx].symbol), *range),
+        Currency((idx, range)) => (format!("Currency({})", currency::CURRENCIES[*idx].code), *range),
+        Variable((variable, range)) => (format!("Variable({})", variable.name), *range),
+        Open(range) => ("Open".to_string(), *range),
+        Close(range) => ("Close".to_string(), *range),
+        Separator(range) => ("Separator".to_string(), *range),
+        If(range) => ("If".to_string(), *range),
+        Then(range) => ("Then".to_string(), *range),
+        Else(range) => ("Else".to_string(), *range),
+    }
+}
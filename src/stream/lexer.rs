@@ -0,0 +1,78 @@
+use super::super::token::InputToken;
+use super::super::types::StartEnd;
+
+/// Zerlegt die Eingabe ohne Allokation in [`InputToken`], jedes zeigt auf
+/// seinen Abschnitt der Eingabe.
+#[derive(Debug, Clone)]
+pub struct Lexer<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+        }
+    }
+
+    /// Setzt die Zerlegung ab Byte `pos` fort, z.B.: nach einem Datum.
+    pub fn seek(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    /// Zeichen ab Byte `pos`.
+    pub fn char_at(&self, pos: usize) -> Option<char> {
+        self.input.get(pos..).and_then(|rest| rest.chars().next())
+    }
+
+    /// Ende der Zeichen ab Byte `pos` für welche `accept` zutrifft.
+    pub fn end_of(&self, pos: usize, accept: impl Fn(char) -> bool) -> usize {
+        match self.input[pos..].find(|c| !accept(c)) {
+            Some(len) => pos + len,
+            None => self.input.len()
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = InputToken<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use InputToken::*;
+
+        let start = self.pos;
+        let c = self.char_at(start)?;
+        let end = match c {
+            ' ' => self.end_of(start, |c| c == ' '),
+            c if is_letter(c) => self.end_of(start, is_letter),
+            '0'..='9' => self.end_of(start, |c| c.is_ascii_digit()),
+            c => start + c.len_utf8()
+        };
+        self.pos = end;
+
+        let range = StartEnd::from(start, end);
+        let text = &self.input[start..end];
+        let token = match c {
+            ' ' => Space(range),
+            c if is_letter(c) => Letters((text, range)),
+            '0'..='9' => Digits((text, range)),
+            c if is_symbol(c) => Symbol((c, range)),
+            c => Whatever((c, range))
+        };
+        Some(token)
+    }
+}
+
+pub fn is_letter(c: char) -> bool {
+    matches!(c, 'a'..='z' | 'A'..='Z' | 'ö' | 'ä' | 'ü' | 'Ö' | 'Ä' | 'Ü' | 'ß')
+}
+
+fn is_symbol(c: char) -> bool {
+    matches!(
+        c,
+        '+' | '-' | '*' | '×' | '÷' | '/' | '(' | ')' | '%' | '‰' | '!' | '^' | ';' | '&' | '|' |
+        '<' | '>' | ',' | '.' | '=' | '≠' | '≤' | '≥' | '?' | '€' | '$' | '£' | '¥'
+    )
+}
@@ -1,7 +1,9 @@
-mod input;
+mod lexer;
 mod parse;
 mod word;
+mod debug;
 
-pub use input::*;
+pub use lexer::*;
 pub use parse::*;
-pub use word::*;
\ No newline at end of file
+pub use word::*;
+pub use debug::*;
@@ -1,95 +1,276 @@
-use super::input::InputStream;
+use std::borrow::Cow;
+
+use super::lexer::{Lexer, is_letter};
 use super::word::WordTokenStream;
 use super::super::token::{ParseToken, InputToken, WordToken};
-use super::super::types::{Operator, StartEnd};
+use super::super::types::{Operator, PrefixOperator, PostfixOperator, Function, StartEnd, Variable};
 use super::super::error::{Error};
 use super::super::error::ErrorStruct;
 use super::super::parse::ParseTree;
+use super::super::unit;
+use super::super::currency;
+use super::super::decimal::Decimal;
+use super::super::date::Date;
+use super::super::settings::Settings;
 
-pub struct ParseStream {
+pub struct ParseStream<'a> {
     data: Vec<ParseToken>,
-    input: InputStream,
+    input: &'a str,
 }
 
-impl ParseStream {
+impl<'a> ParseStream<'a> {
     pub fn new() -> Self {
         Self {
             data: Vec::<ParseToken>::new(),
-            input: InputStream::new(),
+            input: "",
         }
     }
 
-    pub fn from(input: &InputStream) -> Result<Self, Error> {
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn from(input: &'a str) -> Result<Self, Error<'a>> {
+        Self::with_variables(input, &[])
+    }
+
+    /// Wie [`ParseStream::from`], die Wörter in `variables` werden zu
+    /// [`ParseToken::Variable`]. Die Namen sind klein geschrieben.
+    pub fn with_variables(input: &'a str, variables: &[&'static str]) -> Result<Self, Error<'a>> {
         use InputToken::*;
 
         let mut data = Vec::new();
         let mut errors = Vec::new();
-        let iter = input.iter().enumerate();
-        let mut skip = 0usize;
-        for (idx, token) in iter {
-            if idx < skip { continue }
+        let mut percent_signs = Vec::new();
+        let mut and_words = Vec::new();
+        let mut buffer = [0u8; 64];
+        let mut lexer = Lexer::new(input);
+        while let Some(token) = lexer.next() {
             match token {
-                Space => {}
-                Letter(_) => {
-                    let start = idx;
-                    let mut end = idx;
-                    let mut literal = String::new();
-                    let iter = input.iter().enumerate().skip(idx);
-                    for (idx, token) in iter {
-                        match token {
-                            Letter(c) => {
-                                literal.push(*c);
-                                skip = idx +1;
-                                end = skip;
-                            }
-                            _ => break
+                Space(_) => {}
+                Letters((literal, range)) => {
+                    let literal = lowercase(literal, &mut buffer);
+                    if let Some(idx) = variables.iter().position(|name| *name == literal.as_ref()) {
+                        let variable = Variable { name: variables[idx], idx };
+                        data.push(ParseToken::Variable((variable, range)));
+                        continue
+                    }
+                    match literal.as_ref() {
+                        // `ist 3 mal 4 größer als 10?`
+                        "ist" => continue,
+                        // `sinus von x` is `sinus(x)`
+                        "von" if matches!(data.last(), Some(ParseToken::Function((function, _))) if function.is_unary()) => continue,
+                        // `kleiner`, `größer`, `kleiner als`, `größer als`, `kleiner gleich` and `größer gleich`
+                        "kleiner" |
+                        "größer" => {
+                            let mut ahead = lexer.clone();
+                            let word = match (ahead.next(), ahead.next()) {
+                                (Some(Space(_)), Some(Letters((word, word_range)))) => Some((word, word_range.end)),
+                                _ => None
+                            };
+                            let less = literal.as_ref() == "kleiner";
+                            let (op, end) = match word {
+                                Some((word, end)) if word.eq_ignore_ascii_case("als") => {
+                                    lexer = ahead;
+                                    (if less { Operator::Less } else { Operator::Greater }, end)
+                                }
+                                Some((word, end)) if word.eq_ignore_ascii_case("gleich") => {
+                                    lexer = ahead;
+                                    (if less { Operator::LessEqual } else { Operator::GreaterEqual }, end)
+                                }
+                                _ => (if less { Operator::Less } else { Operator::Greater }, range.end),
+                            };
+                            data.push(ParseToken::Op((op, StartEnd::from(range.start, end))));
+                            continue
                         }
+                        _ => {}
                     }
-                    match parse_word(literal.as_str(), StartEnd::from(start, end)) {
-                        Ok(t) => data.push(t),
-                        Err(msg) => errors.push(ErrorStruct::new(StartEnd::from(start, end), msg))
+                    match parse_word(literal.as_ref(), range) {
+                        Ok(t) => {
+                            // `und` is resolved after all tokens are known
+                            if let ParseToken::Separator(_) = t {
+                                and_words.push(data.len());
+                            }
+                            data.push(t)
+                        }
+                        Err(e) => errors.push(e)
                     }
                 }
-                Digit(_) => {
-                    let start = idx;
-                    let mut end = idx;
-                    let mut literal = String::new();
-                    let iter = input.iter().enumerate().skip(idx);
-                    for (idx, token) in iter {
-                        match token {
-                            Digit(c) => {
-                                literal.push(*c);
-                                skip = idx +1;
-                                end = skip;
+                Digits((digits, range)) => {
+                    let start = range.start;
+                    let mut literal = digits;
+                    let mut end = range.end;
+                    let mut radix = 10;
+                    match (digits, lexer.char_at(end)) {
+                        // 0x1F, 0b1010 and 0o17, without a digit `0x` is `0` followed by the variable `x`
+                        ("0", Some(c @ ('x' | 'b' | 'o'))) if lexer.char_at(end +1).is_some_and(|d| d.is_ascii_digit() || (c == 'x' && d.is_ascii_hexdigit())) => {
+                            radix = match c {
+                                'x' => 16,
+                                'b' => 2,
+                                _ => 8
+                            };
+                            end = lexer.end_of(end +1, |c| c.is_ascii_digit() || is_letter(c));
+                            literal = &input[range.end +1..end];
+                        }
+                        // 1F hex, 1010 bin and 17 okt
+                        _ => {
+                            let digits_end = lexer.end_of(start, |c| c.is_ascii_hexdigit());
+                            let word_start = lexer.end_of(digits_end, |c| c == ' ');
+                            let word_end = lexer.end_of(word_start, is_letter);
+                            let word = &input[word_start..word_end];
+                            let suffix = match word {
+                                _ if word.eq_ignore_ascii_case("hex") => Some(16),
+                                _ if word.eq_ignore_ascii_case("bin") => Some(2),
+                                _ if word.eq_ignore_ascii_case("okt") => Some(8),
+                                _ => None
+                            };
+                            if let Some(suffix) = suffix {
+                                radix = suffix;
+                                literal = &input[start..digits_end];
+                                end = word_end;
                             }
-                            _ => break
                         }
                     }
-                    match parse_number(literal.as_str(), StartEnd::from(start, end)) {
+                    // 17.10.2026
+                    if let (10, Some('.'), Some('0'..='9')) = (radix, lexer.char_at(end), lexer.char_at(end +1)) {
+                        let month_end = lexer.end_of(end +1, |c| c.is_ascii_digit());
+                        let year_end = match (lexer.char_at(month_end), lexer.char_at(month_end +1)) {
+                            (Some('.'), Some('0'..='9')) => lexer.end_of(month_end +1, |c| c.is_ascii_digit()),
+                            _ => month_end
+                        };
+                        let year = input.get(month_end +1..year_end).unwrap_or("");
+                        lexer.seek(year_end);
+                        let range = StartEnd::from(start, year_end);
+                        match parse_date(literal, &input[end +1..month_end], year) {
+                            Ok(date) => data.push(ParseToken::Date((date, range))),
+                            Err(msg) => errors.push(ErrorStruct::new(range, msg))
+                        }
+                        continue
+                    }
+                    // 12,50
+                    if let (10, Some(','), Some('0'..='9')) = (radix, lexer.char_at(end), lexer.char_at(end +1)) {
+                        let fraction_end = lexer.end_of(end +1, |c| c.is_ascii_digit());
+                        lexer.seek(fraction_end);
+                        let range = StartEnd::from(start, fraction_end);
+                        match Decimal::parse(&input[start..fraction_end]) {
+                            Some(d) => data.push(ParseToken::Decimal((d, range))),
+                            None => errors.push(ErrorStruct::new(range, "Zahl zu groß".to_string()))
+                        }
+                        continue
+                    }
+                    lexer.seek(end);
+                    match parse_number(literal, radix, StartEnd::from(start, end)) {
                         Ok(t) => data.push(t),
                         Err(msg) => errors.push(ErrorStruct::new(StartEnd::from(start, end), msg))
                     }
                 }
-                Symbol(s) => {
+                Symbol((s, range)) => {
                     match s {
-                        '+' => data.push(ParseToken::Op((Operator::Plus, StartEnd::from(idx, idx+1)))),
-                        '-' => data.push(ParseToken::Op((Operator::Minus, StartEnd::from(idx, idx+1)))),
-                        '*' => data.push(ParseToken::Op((Operator::Multiply, StartEnd::from(idx, idx+1)))),
-                        '/' => data.push(ParseToken::Op((Operator::Divide, StartEnd::from(idx, idx+1)))),
-                        '(' => data.push(ParseToken::Open(StartEnd::from(idx, idx+1))),
-                        ')' => data.push(ParseToken::Close(StartEnd::from(idx, idx+1))),
+                        '+' => data.push(ParseToken::Op((Operator::Plus, range))),
+                        '-' => data.push(ParseToken::Op((Operator::Minus, range))),
+                        '*' |
+                        '×' => data.push(ParseToken::Op((Operator::Multiply, range))),
+                        '/' |
+                        '÷' => data.push(ParseToken::Op((Operator::Divide, range))),
+                        '(' => data.push(ParseToken::Open(range)),
+                        ')' => data.push(ParseToken::Close(range)),
+                        '%' => {
+                            percent_signs.push(data.len());
+                            data.push(ParseToken::Postfix((PostfixOperator::Percent, range)))
+                        }
+                        '‰' => data.push(ParseToken::Postfix((PostfixOperator::Permille, range))),
+                        '!' => data.push(ParseToken::Postfix((PostfixOperator::Factorial, range))),
+                        ';' => data.push(ParseToken::Separator(range)),
+                        '^' => data.push(ParseToken::Op((Operator::Power, range))),
+                        '&' => data.push(ParseToken::Op((Operator::BitAnd, range))),
+                        '|' => data.push(ParseToken::Op((Operator::BitOr, range))),
+                        '<' |
+                        '>' => {
+                            let op = match (s, lexer.char_at(range.end)) {
+                                ('<', Some('<')) => Some(Operator::ShiftLeft),
+                                ('>', Some('>')) => Some(Operator::ShiftRight),
+                                ('<', Some('=')) => Some(Operator::LessEqual),
+                                ('>', Some('=')) => Some(Operator::GreaterEqual),
+                                _ => None
+                            };
+                            match op {
+                                Some(op) => {
+                                    data.push(ParseToken::Op((op, StartEnd::from(range.start, range.end +1))));
+                                    lexer.seek(range.end +1);
+                                }
+                                None => {
+                                    let op = match s {
+                                        '<' => Operator::Less,
+                                        _ => Operator::Greater
+                                    };
+                                    data.push(ParseToken::Op((op, range)));
+                                }
+                            }
+                        }
+                        '=' => data.push(ParseToken::Op((Operator::Equal, range))),
+                        '≠' => data.push(ParseToken::Op((Operator::NotEqual, range))),
+                        '≤' => data.push(ParseToken::Op((Operator::LessEqual, range))),
+                        '≥' => data.push(ParseToken::Op((Operator::GreaterEqual, range))),
+                        // `ist 3 mal 4 größer als 10?`
+                        '?' => {
+                            if lexer.end_of(range.end, |c| c == ' ') < input.len() {
+                                errors.push(ErrorStruct::new(range, "Fragezeichen nur am Ende möglich".to_string()))
+                            }
+                        }
+                        '.' => errors.push(ErrorStruct::new(range, "Punkt nur in einem Datum möglich, z.B.: 17.10.2026".to_string())),
+                        ',' => errors.push(ErrorStruct::new(range, "Komma nur zwischen Ziffern möglich".to_string())),
+                        '€' |
+                        '$' |
+                        '£' |
+                        '¥' => {
+                            let currency = currency::find(&input[range.start..range.end]).unwrap();
+                            data.push(ParseToken::Currency((currency, range)))
+                        }
                         _ => unreachable!()
                     }
                 }
-                Whatever(w) => errors.push(ErrorStruct::new(StartEnd::from(idx, idx +1), "Unbekanntes Zeichen".to_string())),
+                Whatever((_, range)) => errors.push(ErrorStruct::new(range, "Unbekanntes Zeichen".to_string())),
             }
         }
 
-        if errors.len() > 0 { return Err(Error::TokenStream(input.clone(), errors)) } 
+        if !errors.is_empty() { return Err(Error::TokenStream(input, errors)) } 
+
+        // A `%` followed by an operand is the modulo operator, e.g. `7 % 3`,
+        // before `resolve_between` inserts tokens and shifts the indices
+        for idx in percent_signs {
+            if let ParseToken::Postfix((_, range)) = data[idx] {
+                match data.get(idx +1) {
+                    Some(ParseToken::Number(_)) |
+                    Some(ParseToken::Variable(_)) |
+                    Some(ParseToken::Decimal(_)) |
+                    Some(ParseToken::Date(_)) |
+                    Some(ParseToken::Open(_)) |
+                    Some(ParseToken::Function(_)) => data[idx] = ParseToken::Op((Operator::Modulo, range)),
+                    _ => {}
+                }
+            }
+        }
+
+        let data = match resolve_between(data, and_words) {
+            Ok(data) => data,
+            Err(e) => return Err(Error::TokenStream(input, vec![e]))
+        };
+
+        // `2 h 30 min` is `2 h + 30 min`
+        let mut tokens = Vec::with_capacity(data.len());
+        let mut data = data.into_iter().peekable();
+        while let Some(token) = data.next() {
+            if let (Some(ParseToken::Unit(_)), ParseToken::Number((_, range)) | ParseToken::Decimal((_, range)), Some(ParseToken::Unit(_))) = (tokens.last(), &token, data.peek()) {
+                let range = StartEnd::from(range.start, range.start);
+                tokens.push(ParseToken::Op((Operator::Compound, range)));
+            }
+            tokens.push(token);
+        }
+        let data = tokens;
 
         Ok(Self {
             data,
-            input: input.clone()
+            input
         })
     }
 
@@ -113,24 +294,53 @@ impl ParseStream {
         self.data.clear();
     }
 
+    /// Fügt zwischen zwei direkt aufeinander folgenden Operanden einen
+    /// [`Operator::ImplicitMultiply`] ein, z.B.: `2(3+4)` wird zu `2×(3+4)`.
+    /// Der eingefügte Operator erhält eine leere Position am Anfang des
+    /// rechten Operanden.
+    pub fn insert_implicit_multiplication(&mut self) {
+        use ParseToken::*;
+
+        let mut data = Vec::with_capacity(self.data.len());
+        for token in self.data.drain(..) {
+            if let Some(Number(_) | Decimal(_) | Date(_) | Variable(_) | Close(_) | Postfix(_) | Unit(_) | Currency(_)) = data.last() {
+                if let Number((_, range)) | Decimal((_, range)) | Date((_, range)) | Variable((_, range)) | Open(range) | Function((_, range)) = &token {
+                    let range = StartEnd::from(range.start, range.start);
+                    data.push(Op((Operator::ImplicitMultiply, range)));
+                }
+            }
+            data.push(token);
+        }
+        self.data = data;
+    }
+
     fn iter(&self) -> std::slice::Iter<'_, ParseToken> {
         self.data.iter()
     }
 
-    pub fn validate(&self) -> Result<(), Error> {
+    pub fn validate(&self) -> Result<(), Error<'a>> {
         use ParseToken::*;
 
         let mut error_vec = Vec::new();
+        // Open parentheses without a matching close so far
+        let mut open = Vec::new();
 
         for (idx, token) in self.iter().enumerate() {
             match token {
-                Number((_, range)) => {
-                    if idx != 0 {
-                        match self[idx -1] {
-                            Op(_) => {}
-                            Open(_) => {}
-                            _ => error_vec.push(ErrorStruct::new(*range, "Operand hier nicht möglich".to_string()))
-                        }
+                Number((_, range)) |
+                Decimal((_, range)) |
+                Date((_, range)) |
+                Variable((_, range)) if idx != 0 => {
+                    match self[idx -1] {
+                        Op(_) => {}
+                        Open(_) => {}
+                        Separator(_) => {}
+                        Prefix(_) => {}
+                        If(_) => {}
+                        Then(_) => {}
+                        Else(_) => {}
+                        Function((function, _)) if function.is_unary() => {}
+                        _ => error_vec.push(ErrorStruct::new(*range, "Operand hier nicht möglich".to_string()))
                     }
                 }
                 Op((op, range)) => {
@@ -142,31 +352,58 @@ impl ParseStream {
                                 //     _ => error_vec.push(ErrorStruct::new(*range, "Prefix Operator hier nicht möglich".to_string()))
                                 // }
                             // }
+                            // `-0x8000000000000000` is 2^63 as the literal wraps to `i64::MIN`
+                            let is_prefix = !matches!(idx.checked_sub(1).map(|idx| &self[idx]), Some(Number(_) | Decimal(_) | Date(_) | Variable(_) | Close(_) | Postfix(_) | Unit(_) | Currency(_)));
                             if idx +1 < self.data.len() {
                                 match self[idx +1] {
+                                    Number((i64::MIN, number)) if is_prefix => {
+                                        error_vec.push(ErrorStruct::new(StartEnd::from(range.start, number.end), "Zahl zu groß".to_string()))
+                                    }
                                     Number(_) => {}
+                                    Variable(_) => {}
+                                    Decimal(_) => {}
+                                    Date(_) => {}
                                     Open(_) => {}
+                                    Function(_) => {}
+                                    Prefix(_) => {}
+                                    If(_) => {}
+                                    Unit(_) => {}
+                                    Currency(_) => {}
                                     _ => error_vec.push(ErrorStruct::new(*range, "Operator hat keinen Operanden".to_string()))
                                 }
                             } else {
                                 error_vec.push(ErrorStruct::new(*range, "Operator hat keinen Operanden".to_string()))
                             }
                         }
-                        Operator::Plus |
-                        Operator::Multiply |
-                        Operator::Divide => {
+                        _ => {
                             if idx == 0 {
                                 error_vec.push(ErrorStruct::new(*range, "Operator hier nicht möglich".to_string()))
                             }
                             else if idx +1 < self.data.len() {
                                 match self[idx -1] {
                                     Number(_) => {}
+                                    Variable(_) => {}
+                                    Decimal(_) => {}
+                                    Date(_) => {}
                                     Close(_) => {}
+                                    Postfix(_) => {}
+                                    Unit(_) => {}
+                                    Currency(_) => {}
                                     _ => error_vec.push(ErrorStruct::new(*range, "Operator hier nicht möglich".to_string()))
                                 }
                                 match self[idx +1] {
                                     Number(_) => {}
+                                    Variable(_) => {}
+                                    Decimal(_) => {}
+                                    Date(_) => {}
                                     Open(_) => {}
+                                    Function(_) => {}
+                                    Prefix(_) => {}
+                                    If(_) => {}
+                                    Unit(_) => {}
+                                    Currency(_) => {}
+                                    // `x = -3` like `-3` at the start of the input
+                                    Op((Operator::Minus, _)) if op.is_comparison() => {}
                                     _ => error_vec.push(ErrorStruct::new(*range, "Operator hat keinen Operanden".to_string()))
                                 }
                             } 
@@ -182,15 +419,21 @@ impl ParseStream {
                         match self[idx -1] {
                             Op(_) => {}
                             Open(_) => {}
+                            Function(_) => {}
+                            Separator(_) => {}
+                            Prefix(_) => {}
+                            If(_) => {}
+                            Then(_) => {}
+                            Else(_) => {}
                             _ => error_vec.push(ErrorStruct::new(*range, "Klammer hier nicht möglich".to_string()))
                         }
                     }
-                    match self.find_close(idx) {
-                        Some(_) => {}
-                        None => error_vec.push(ErrorStruct::new(*range, "Schließende Klammer fehlt".to_string()))
-                    }
+                    open.push(*range);
                 }
                 Close(range) => {
+                    if open.pop().is_none() {
+                        error_vec.push(ErrorStruct::new(*range, "Öffnende Klammer fehlt".to_string()))
+                    }
                     if idx == 0 {
                         error_vec.push(ErrorStruct::new(*range, "Schließende Klammer hier nicht möglich".to_string()))
                     }
@@ -198,40 +441,229 @@ impl ParseStream {
                         match self[idx -1] {
                             Close(_) => {}
                             Number(_) => {}
+                            Variable(_) => {}
+                            Decimal(_) => {}
+                            Date(_) => {}
+                            Postfix(_) => {}
+                            Unit(_) => {}
+                            Currency(_) => {}
                             _ => error_vec.push(ErrorStruct::new(*range, "Schließende Klammer hier nicht möglich".to_string()))
                         }
                     }
                 }
+                Postfix((_, range)) => {
+                    if idx == 0 {
+                        error_vec.push(ErrorStruct::new(*range, "Operator hat keinen Operanden".to_string()))
+                    } else {
+                        match self[idx -1] {
+                            Close(_) => {}
+                            Number(_) => {}
+                            Variable(_) => {}
+                            Decimal(_) => {}
+                            Date(_) => {}
+                            Postfix(_) => {}
+                            Unit(_) => {}
+                            Currency(_) => {}
+                            _ => error_vec.push(ErrorStruct::new(*range, "Operator hat keinen Operanden".to_string()))
+                        }
+                    }
+                }
+                Function((function, range)) => {
+                    if idx > 0 {
+                        match self[idx -1] {
+                            Op(_) => {}
+                            Open(_) => {}
+                            Separator(_) => {}
+                            Prefix(_) => {}
+                            If(_) => {}
+                            Then(_) => {}
+                            Else(_) => {}
+                            Function((function, _)) if function.is_unary() => {}
+                            _ => error_vec.push(ErrorStruct::new(*range, "Funktion hier nicht möglich".to_string()))
+                        }
+                    }
+                    match self.data.get(idx +1) {
+                        Some(Open(_)) => {}
+                        // `sinus von x`
+                        Some(Number(_) | Decimal(_) | Variable(_) | Function(_)) if function.is_unary() => {}
+                        _ => error_vec.push(ErrorStruct::new(*range, "Funktion erwartet Klammer".to_string()))
+                    }
+                }
+                Separator(range) => {
+                    match idx.checked_sub(1).map(|idx| &self[idx]) {
+                        Some(Number(_)) |
+                        Some(Variable(_)) |
+                        Some(Decimal(_)) |
+                        Some(Date(_)) |
+                        Some(Close(_)) |
+                        Some(Postfix(_)) |
+                        Some(Unit(_)) |
+                        Some(Currency(_)) => {}
+                        _ => error_vec.push(ErrorStruct::new(*range, "Trennzeichen hier nicht möglich".to_string()))
+                    }
+                    match self.data.get(idx +1) {
+                        Some(Number(_)) |
+                        Some(Variable(_)) |
+                        Some(Decimal(_)) |
+                        Some(Date(_)) |
+                        Some(Open(_)) |
+                        Some(Function(_)) |
+                        Some(Prefix(_)) |
+                        Some(Unit(_)) |
+                        Some(Currency(_)) |
+                        Some(If(_)) |
+                        Some(Op((Operator::Minus, _))) => {}
+                        _ => error_vec.push(ErrorStruct::new(*range, "Trennzeichen hier nicht möglich".to_string()))
+                    }
+                }
+                Prefix((_, range)) => {
+                    if idx > 0 {
+                        match self[idx -1] {
+                            Op(_) => {}
+                            Open(_) => {}
+                            Separator(_) => {}
+                            Prefix(_) => {}
+                            If(_) => {}
+                            Then(_) => {}
+                            Else(_) => {}
+                            _ => error_vec.push(ErrorStruct::new(*range, "Operator hier nicht möglich".to_string()))
+                        }
+                    }
+                    match self.data.get(idx +1) {
+                        Some(Number(_)) |
+                        Some(Variable(_)) |
+                        Some(Decimal(_)) |
+                        Some(Date(_)) |
+                        Some(Open(_)) |
+                        Some(Function(_)) |
+                        Some(Prefix(_)) |
+                        Some(Unit(_)) |
+                        Some(Currency(_)) |
+                        Some(If(_)) |
+                        Some(Op((Operator::Minus, _))) => {}
+                        _ => error_vec.push(ErrorStruct::new(*range, "Operator hat keinen Operanden".to_string()))
+                    }
+                }
+                Unit((_, range)) |
+                Currency((_, range)) if idx > 0 => {
+                    match self[idx -1] {
+                        Unit(_) |
+                        Currency(_) => error_vec.push(ErrorStruct::new(*range, "Einheit hier nicht möglich".to_string())),
+                        _ => {}
+                    }
+                }
+                If(range) if idx > 0 => {
+                    match self[idx -1] {
+                        Op(_) => {}
+                        Open(_) => {}
+                        Separator(_) => {}
+                        Prefix(_) => {}
+                        If(_) => {}
+                        Then(_) => {}
+                        Else(_) => {}
+                        _ => error_vec.push(ErrorStruct::new(*range, "\"wenn\" hier nicht möglich".to_string()))
+                    }
+                }
+                Then(range) |
+                Else(range) => {
+                    let word = match token {
+                        Then(_) => "dann",
+                        _ => "sonst"
+                    };
+                    match idx.checked_sub(1).map(|idx| &self[idx]) {
+                        Some(Number(_)) |
+                        Some(Variable(_)) |
+                        Some(Decimal(_)) |
+                        Some(Date(_)) |
+                        Some(Close(_)) |
+                        Some(Postfix(_)) |
+                        Some(Unit(_)) |
+                        Some(Currency(_)) => {}
+                        _ => error_vec.push(ErrorStruct::new(*range, format!("\"{}\" hier nicht möglich", word)))
+                    }
+                    match self.data.get(idx +1) {
+                        Some(Number(_)) |
+                        Some(Variable(_)) |
+                        Some(Decimal(_)) |
+                        Some(Date(_)) |
+                        Some(Open(_)) |
+                        Some(Function(_)) |
+                        Some(Prefix(_)) |
+                        Some(Unit(_)) |
+                        Some(Currency(_)) |
+                        Some(If(_)) |
+                        Some(Op((Operator::Minus, _))) => {}
+                        _ => error_vec.push(ErrorStruct::new(*range, format!("\"{}\" hat keinen Operanden", word)))
+                    }
+                }
                 _ => {}        
             }
         }          
+
+        for range in open {
+            error_vec.push(ErrorStruct::new(range, "Schließende Klammer fehlt".to_string()));
+        }
+        self.validate_conditions(&mut error_vec);
         
-        if error_vec.len() > 0 {
-            return Err(Error::TokenStream(self.input.clone(), error_vec))
+        if !error_vec.is_empty() {
+            return Err(Error::TokenStream(self.input, error_vec))
         }
     
         Ok(())
     }
     
-    fn find_close(&self, skip: usize) -> Option<()> {
-        let mut open_count = 0;
-        for token in self.iter().skip(skip) {
+    /// Prüft ob zu jedem `wenn` ein `dann` und ein `sonst` innerhalb der
+    /// selben Klammer gehört.
+    fn validate_conditions(&self, error_vec: &mut Vec<ErrorStruct>) {
+        use ParseToken::*;
+
+        // Open conditions with their parenthesis depth and the range of the last keyword
+        let mut stack: Vec<(usize, bool, StartEnd)> = Vec::new();
+        let mut depth = 0;
+        for token in self.iter() {
             match token {
-                ParseToken::Open(_) => open_count += 1,
-                ParseToken::Close(_) => {
-                    open_count -= 1;
-                    if open_count == 0 {
-                        return Some(())
+                Open(_) => depth += 1,
+                Close(_) => {
+                    Self::missing_keywords(&mut stack, depth, error_vec);
+                    depth = depth.saturating_sub(1);
+                }
+                If(range) => stack.push((depth, false, *range)),
+                Then(range) => {
+                    match stack.last_mut() {
+                        Some((d, then, r)) if *d == depth && !*then => {
+                            *then = true;
+                            *r = *range;
+                        }
+                        _ => error_vec.push(ErrorStruct::new(*range, "\"dann\" ohne \"wenn\"".to_string()))
+                    }
+                }
+                Else(range) => {
+                    match stack.last() {
+                        Some((d, true, _)) if *d == depth => {
+                            stack.pop();
+                        }
+                        _ => error_vec.push(ErrorStruct::new(*range, "\"sonst\" ohne \"wenn ... dann\"".to_string()))
                     }
                 }
                 _ => {}
             }
-        }              
-        None          
+        }
+        Self::missing_keywords(&mut stack, 0, error_vec);
+    }
+
+    fn missing_keywords(stack: &mut Vec<(usize, bool, StartEnd)>, depth: usize, error_vec: &mut Vec<ErrorStruct>) {
+        while let Some((d, then, range)) = stack.last() {
+            if *d < depth { break }
+            match then {
+                false => error_vec.push(ErrorStruct::new(*range, "\"wenn\" ohne \"dann\"".to_string())),
+                true => error_vec.push(ErrorStruct::new(*range, "\"dann\" ohne \"sonst\"".to_string())),
+            }
+            stack.pop();
+        }
     }
 }
 
-impl<'a> IntoIterator for &'a ParseStream {
+impl<'a> IntoIterator for &'a ParseStream<'_> {
     type Item = &'a ParseToken;
     type IntoIter = std::slice::Iter<'a, ParseToken>;
 
@@ -240,7 +672,7 @@ impl<'a> IntoIterator for &'a ParseStream {
     }
 }
 
-impl std::ops::Index<usize> for ParseStream {
+impl std::ops::Index<usize> for ParseStream<'_> {
     type Output = ParseToken;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -248,7 +680,7 @@ impl std::ops::Index<usize> for ParseStream {
     }
 }
 
-impl std::ops::Index<std::ops::Range<usize>> for ParseStream {
+impl std::ops::Index<std::ops::Range<usize>> for ParseStream<'_> {
     type Output = [ParseToken];
 
     fn index(&self, index: std::ops::Range<usize>) -> &Self::Output {
@@ -256,7 +688,7 @@ impl std::ops::Index<std::ops::Range<usize>> for ParseStream {
     }
 }
 
-impl std::ops::Index<std::ops::RangeFrom<usize>> for ParseStream {
+impl std::ops::Index<std::ops::RangeFrom<usize>> for ParseStream<'_> {
     type Output = [ParseToken];
 
     fn index(&self, index: std::ops::RangeFrom<usize>) -> &Self::Output {
@@ -264,7 +696,7 @@ impl std::ops::Index<std::ops::RangeFrom<usize>> for ParseStream {
     }
 }
 
-impl std::ops::Index<std::ops::RangeTo<usize>> for ParseStream {
+impl std::ops::Index<std::ops::RangeTo<usize>> for ParseStream<'_> {
     type Output = [ParseToken];
 
     fn index(&self, index: std::ops::RangeTo<usize>) -> &Self::Output {
@@ -272,7 +704,7 @@ impl std::ops::Index<std::ops::RangeTo<usize>> for ParseStream {
     }
 }
 
-impl std::ops::Index<std::ops::RangeFull> for ParseStream {
+impl std::ops::Index<std::ops::RangeFull> for ParseStream<'_> {
     type Output = [ParseToken];
 
     fn index(&self, index: std::ops::RangeFull) -> &Self::Output {
@@ -280,30 +712,128 @@ impl std::ops::Index<std::ops::RangeFull> for ParseStream {
     }
 }
 
-fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
+fn parse_date(day: &str, month: &str, year: &str) -> Result<Date, String> {
+    if year.is_empty() {
+        return Err("Datum erwartet Tag, Monat und Jahr, z.B.: 17.10.2026".to_string())
+    }
+    match (day.parse::<u32>(), month.parse::<u32>(), year.parse::<i64>()) {
+        (Ok(day), Ok(month), Ok(year)) if (1..=9999).contains(&year) => {
+            match Date::from(day, month, year) {
+                Some(date) => Ok(date),
+                None => Err("Ungültiges Datum".to_string())
+            }
+        }
+        _ => Err("Ungültiges Datum".to_string())
+    }
+}
+
+/// Wandelt `zwischen a und b` in den Funktionsaufruf `zwischen(a; b)` um.
+/// Das zweite Argument reicht bis zum Ende der umgebenden Klammer oder bis
+/// zum nächsten `und`. Steht davor eine Einheit, wird das Ergebnis in diese
+/// umgerechnet, aus `Tage zwischen a und b` wird `zwischen(a; b) in Tage`.
+/// Jedes andere alleinstehende `und` ist das logische `und`, innerhalb
+/// eines Zahlworts wie `dreiundzwanzig` wird es nie als Operator gelesen.
+fn resolve_between(mut data: Vec<ParseToken>, and_words: Vec<usize>) -> Result<Vec<ParseToken>, ErrorStruct> {
+    use ParseToken::*;
+
+    let mut is_and = vec![false; data.len()];
+    for idx in and_words {
+        is_and[idx] = true;
+    }
+
+    // From right to left, inserted tokens only shift the following indices
+    let mut idx = data.len();
+    while idx > 0 {
+        idx -= 1;
+        let range = match data[idx] {
+            Function((super::super::types::Function::Between, range)) => range,
+            _ => continue
+        };
+
+        let mut depth = 0;
+        let mut and_idx = None;
+        let mut end = data.len();
+        for (i, token) in data.iter().enumerate().skip(idx +1) {
+            match token {
+                Open(_) => depth += 1,
+                Close(_) if depth == 0 => {
+                    end = i;
+                    break
+                }
+                Close(_) => depth -= 1,
+                Separator(_) if depth == 0 && is_and[i] && and_idx.is_none() => and_idx = Some(i),
+                Separator(_) |
+                If(_) |
+                Then(_) |
+                Else(_) if depth == 0 => {
+                    end = i;
+                    break
+                }
+                _ => {}
+            }
+        }
+        let and_idx = match and_idx {
+            Some(and_idx) => and_idx,
+            None => return Err(ErrorStruct::new(range, "\"zwischen\" erwartet \"und\"".to_string()))
+        };
+        is_and[and_idx] = false;
+
+        data.insert(end, Close(StartEnd::from(range.end, range.end)));
+        is_and.insert(end, false);
+        data.insert(idx +1, Open(StartEnd::from(range.end, range.end)));
+        is_and.insert(idx +1, false);
+        if idx > 0 {
+            if let Unit((_, unit_range)) = data[idx -1] {
+                let unit = data.remove(idx -1);
+                is_and.remove(idx -1);
+                // The closing parenthesis moved one to the left
+                data.insert(end +1, Op((Operator::Convert, unit_range)));
+                data.insert(end +2, unit);
+                is_and.insert(end +1, false);
+                is_and.insert(end +2, false);
+                idx -= 1;
+            }
+        }
+    }
+
+    for (token, is_and) in data.iter_mut().zip(is_and) {
+        if let (Separator(range), true) = (&token, is_and) {
+            *token = Op((Operator::And, *range));
+        }
+    }
+    Ok(data)
+}
+
+/// Fehler zeigen bei zusammengesetzten Zahlwörtern auf das Teilwort, z.B.:
+/// bei `dreizehnzig` auf `zig`.
+fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, ErrorStruct> {
+    if let Some(idx) = unit::find(literal) {
+        return Ok(ParseToken::Unit((idx, range)))
+    }
+    if let Some(idx) = currency::find(literal) {
+        return Ok(ParseToken::Currency((idx, range)))
+    }
+
+    // Most words are not compound, this avoids building a `WordTokenStream`
+    if let Some(token) = WordTokenStream::tokinize_single(literal) {
+        return single_word(&token, range)
+    }
+
     let stream = match WordTokenStream::from(literal) {
         Some(s) => s,
-        None => return Err("unbekanntes Wort".to_string())
+        None => return Err(ErrorStruct::new(range, "unbekanntes Wort".to_string()))
     };
-    if stream.len() == 0 { return Err("unbekanntes Wort".to_string()) }
-    if stream.len() == 1 {
-        match stream[0] {
-            WordToken::Plus => return Ok(ParseToken::Op((Operator::Plus, range))),
-            WordToken::Minus => return Ok(ParseToken::Op((Operator::Minus, range))),
-            WordToken::Multiply => return Ok(ParseToken::Op((Operator::Multiply, range))),
-            WordToken::Divide => return Ok(ParseToken::Op((Operator::Divide, range))),
-            WordToken::Open => return Ok(ParseToken::Open(range)),
-            WordToken::Close => return Ok(ParseToken::Close(range)),
-            WordToken::Number(n) => return Ok(ParseToken::Number((n as i64, range))),
-            _ => return Err("unbekanntes Wort".to_string())
-        }
-    }
+    if stream.len() == 0 { return Err(ErrorStruct::new(range, "unbekanntes Wort".to_string())) }
+    if stream.len() == 1 { return single_word(&stream[0], range) }
 
     let mut input = ParseStream::new();
     let iter = stream.triple_iter();
-    for (prev, current, next) in iter {
+    for (idx, (prev, current, next)) in iter.enumerate() {
         use WordToken::*;
-        
+
+        let sub = stream.span(idx);
+        let sub = StartEnd::from(range.start + sub.start, range.start + sub.end);
+
         match prev {
             Some(prev_token) => {
                 match current {
@@ -313,7 +843,7 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                 match prev_token {
                                     Ein |
                                     Number(_) => input.push(ParseToken::Op((Operator::Plus, range))),
-                                    _ => return Err("unbekanntes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                 }
                             }
                             Zehn => {
@@ -322,7 +852,7 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                         input.push(ParseToken::Op((Operator::Plus, range)));
                                         input.push(ParseToken::Number((10, range)));
                                     }
-                                    _ => return Err("unbekanntes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                 }
                             }
                             Zig => {
@@ -331,7 +861,7 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                         input.push(ParseToken::Op((Operator::Multiply, range)));
                                         input.push(ParseToken::Number((10, range)));
                                     }
-                                    _ => return Err("unbekanntes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                 }
                             }
                             SSig => {
@@ -340,7 +870,7 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                         input.push(ParseToken::Op((Operator::Multiply, range)));
                                         input.push(ParseToken::Number((10, range)));
                                     }
-                                    _ => return Err("unbekanntes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                 }
                             }
                             Hundert => {
@@ -350,7 +880,7 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                         input.push(ParseToken::Op((Operator::Multiply, range)));
                                         input.push(ParseToken::Number((100, range)));
                                     }
-                                    _ => return Err("unbekanntes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                 }
                             }
                             Tausend => {
@@ -363,13 +893,13 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                     Zig |
                                     Hundert => {
                                         let tree = ParseTree::from(&input[..]).unwrap();
-                                        let num = tree.evaluate().unwrap();
+                                        let num = tree.evaluate(&Settings::new()).unwrap().number("Zahlwort").and_then(word_number).map_err(|msg| ErrorStruct::new(sub, msg))?;
                                         input.clear();
-                                        input.push(ParseToken::Number((num as i64, range)));
+                                        input.push(ParseToken::Number((num, range)));
                                         input.push(ParseToken::Op((Operator::Multiply, range)));
                                         input.push(ParseToken::Number((1000, range)));
                                     }
-                                    _ => return Err("unbekanntes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                 }
                             }
                             Number(n) => {
@@ -377,33 +907,21 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                     Hundert |
                                     Tausend => {
                                         input.push(ParseToken::Op((Operator::Plus, range)));
-                                        input.push(ParseToken::Number((*n as i64, range)));
+                                        input.push(ParseToken::Number((*n, range)));
                                     }
                                     Und => {
                                         match next {
                                             Some(next_token) => {
                                                 match next_token {
-                                                    SSig => {
-                                                        if *n == 3 {
-                                                            input.push(ParseToken::Number((3, range)))
-                                                        } else {
-                                                            return Err("unbekanntes Wort".to_string())
-                                                        }
-                                                    }
-                                                    Zig => {
-                                                        if *n > 3 {
-                                                            input.push(ParseToken::Number((*n as i64, range)))
-                                                        } else {
-                                                            return Err("unbekanntes Wort".to_string())
-                                                        }
-                                                    }
-                                                    _ => return Err("unbekanntes Wort".to_string())
+                                                    SSig if *n == 3 => input.push(ParseToken::Number((3, range))),
+                                                    Zig if *n > 3 => input.push(ParseToken::Number((*n, range))),
+                                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                                 }
                                             }
-                                            None => return Err("unbekanntes Wort".to_string())
+                                            None => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                         }
                                     }
-                                    _ => return Err("unbekanntes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                 }
                             }
                             Eins |
@@ -420,7 +938,7 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                             _ => unreachable!()
                                         }
                                     }
-                                    _ => return Err("unbekannes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekannes Wort".to_string()))
                                 }
                             }
                             Zwanzig |
@@ -445,42 +963,32 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                                             _ => unreachable!()
                                         }
                                     }
-                                    _ => return Err("unbekannes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekannes Wort".to_string()))
                                 }
                             }
                             Ein => {
                                 match prev_token {
                                     Hundert => {
                                         match next {
-                                            Some(next_token) => {
-                                                match next_token {
-                                                    Und |
-                                                    Tausend => {
-                                                        input.push(ParseToken::Op((Operator::Plus, range)));
-                                                        input.push(ParseToken::Number((1, range)));
-                                                    }
-                                                    _ => return Err("unbekanntes Wort".to_string())
-                                                }
+                                            Some(Und) |
+                                            Some(Tausend) => {
+                                                input.push(ParseToken::Op((Operator::Plus, range)));
+                                                input.push(ParseToken::Number((1, range)));
                                             }
-                                            None => return Err("unbekanntes Wort".to_string())
+                                            _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                         }
                                     }
                                     Tausend => {
                                         match next {
-                                            Some(next_token) => {
-                                                match next_token {
-                                                    Hundert |
-                                                    Und => {
-                                                        input.push(ParseToken::Op((Operator::Plus, range)));
-                                                        input.push(ParseToken::Number((1, range)));
-                                                    }
-                                                    _ => return Err("unbekanntes Wort".to_string())
-                                                }
+                                            Some(Hundert) |
+                                            Some(Und) => {
+                                                input.push(ParseToken::Op((Operator::Plus, range)));
+                                                input.push(ParseToken::Number((1, range)));
                                             }
-                                            None => return Err("unbekanntes Wort".to_string())
+                                            _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                         }
                                     }
-                                    _ => return Err("unbekanntes Wort".to_string())
+                                    _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                                 }
                             }
                             Plus |
@@ -488,7 +996,28 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                             Multiply |
                             Divide |
                             Open |
-                            Close => return Err("unbekanntes Wort".to_string())
+                            Close |
+                            Percent |
+                            Permille |
+                            Of |
+                            Modulo |
+                            IntegerDivide |
+                            Factorial |
+                            Gcd |
+                            Lcm |
+                            WordToken::Function(_) |
+                            Power |
+                            BitXor |
+                            Not |
+                            Convert |
+                            Today |
+                            Between |
+                            Equal |
+                            NotEqual |
+                            Or |
+                            If |
+                            Then |
+                            Else => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                         }
                     }
                     None => unreachable!()
@@ -498,13 +1027,13 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
                 match current {
                     Some(token) => {
                         match token {
-                            Number(n) => input.push(ParseToken::Number((*n as i64, range))),
+                            Number(n) => input.push(ParseToken::Number((*n, range))),
                             Ein => input.push(ParseToken::Number((1, range))),
                             Zehn => input.push(ParseToken::Number((10, range))),
                             Zwanzig => input.push(ParseToken::Number((20, range))),
                             Sechzig => input.push(ParseToken::Number((60, range))),
                             Siebzig => input.push(ParseToken::Number((70, range))),
-                            _ => return Err("unbekanntes Wort".to_string())
+                            _ => return Err(ErrorStruct::new(sub, "unbekanntes Wort".to_string()))
                         }
                     }
                     None => unreachable!()
@@ -515,16 +1044,90 @@ fn parse_word(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
 
     match ParseTree::from(&input[..]) {
         Ok(tree) => {
-            let num = tree.evaluate()?;
-            Ok(ParseToken::Number((num as i64, range)))
+            let num = match tree.evaluate(&Settings::new()) {
+                Ok(value) => value.number("Zahlwort").and_then(word_number).map_err(|msg| ErrorStruct::new(range, msg))?,
+                Err(e) => return Err(ErrorStruct::new(range, e.msg().to_string()))
+            };
+            Ok(ParseToken::Number((num, range)))
         }
         Err(_) => panic!("Expected Ok value")
     }
 }
 
-fn parse_number(literal: &str, range: StartEnd) -> Result<ParseToken, String> {
-    match literal.parse::<u64>() {
-        Ok(n) => Ok(ParseToken::Number((n as i64, range))),
+/// Ein Wort welches nicht aus Teilwörtern besteht, z.B.: `plus` oder `zwölf`.
+fn single_word(token: &WordToken, range: StartEnd) -> Result<ParseToken, ErrorStruct> {
+match token {
+        WordToken::Plus => Ok(ParseToken::Op((Operator::Plus, range))),
+        WordToken::Minus => Ok(ParseToken::Op((Operator::Minus, range))),
+        WordToken::Multiply => Ok(ParseToken::Op((Operator::Multiply, range))),
+        WordToken::Divide => Ok(ParseToken::Op((Operator::Divide, range))),
+        WordToken::Of => Ok(ParseToken::Op((Operator::Multiply, range))),
+        WordToken::Percent => Ok(ParseToken::Postfix((PostfixOperator::Percent, range))),
+        WordToken::Permille => Ok(ParseToken::Postfix((PostfixOperator::Permille, range))),
+        WordToken::Modulo => Ok(ParseToken::Op((Operator::Modulo, range))),
+        WordToken::IntegerDivide => Ok(ParseToken::Op((Operator::IntegerDivide, range))),
+        WordToken::Factorial => Ok(ParseToken::Postfix((PostfixOperator::Factorial, range))),
+        WordToken::Gcd => Ok(ParseToken::Function((Function::Gcd, range))),
+        WordToken::Lcm => Ok(ParseToken::Function((Function::Lcm, range))),
+        WordToken::Function(function) => Ok(ParseToken::Function((*function, range))),
+        WordToken::Power => Ok(ParseToken::Op((Operator::Power, range))),
+        WordToken::BitXor => Ok(ParseToken::Op((Operator::BitXor, range))),
+        WordToken::Not => Ok(ParseToken::Prefix((PrefixOperator::Not, range))),
+        WordToken::Convert => Ok(ParseToken::Op((Operator::Convert, range))),
+        WordToken::Today => Ok(ParseToken::Date((Date::today(), range))),
+        WordToken::Between => Ok(ParseToken::Function((Function::Between, range))),
+        WordToken::Und => Ok(ParseToken::Separator(range)),
+        WordToken::Equal => Ok(ParseToken::Op((Operator::Equal, range))),
+        WordToken::NotEqual => Ok(ParseToken::Op((Operator::NotEqual, range))),
+        WordToken::Or => Ok(ParseToken::Op((Operator::Or, range))),
+        WordToken::If => Ok(ParseToken::If(range)),
+        WordToken::Then => Ok(ParseToken::Then(range)),
+        WordToken::Else => Ok(ParseToken::Else(range)),
+        WordToken::Open => Ok(ParseToken::Open(range)),
+        WordToken::Close => Ok(ParseToken::Close(range)),
+        WordToken::Number(n) => Ok(ParseToken::Number((*n, range))),
+        _ => Err(ErrorStruct::new(range, "unbekanntes Wort".to_string()))
+    }
+}
+
+/// Kleinbuchstaben ohne Allokation, alle Buchstaben aus dem [`Lexer`] haben
+/// klein dieselbe Länge in Bytes. Nur sehr lange Wörter werden kopiert.
+fn lowercase<'b>(literal: &'b str, buffer: &'b mut [u8; 64]) -> Cow<'b, str> {
+    if !literal.chars().any(char::is_uppercase) {
+        return Cow::Borrowed(literal)
+    }
+    if literal.len() > buffer.len() {
+        return Cow::Owned(literal.to_lowercase())
+    }
+    let mut len = 0;
+    for c in literal.chars() {
+        let lower = c.to_lowercase().next().unwrap();
+        len += lower.encode_utf8(&mut buffer[len..]).len();
+    }
+    Cow::Borrowed(std::str::from_utf8(&buffer[..len]).unwrap())
+}
+
+/// Hexadezimal, binär und oktal sind 64 Bit im Zweierkomplement wie bei der
+/// Ausgabe, `0xFFFFFFFFFFFFFFFF` ist somit `-1`.
+fn parse_number(literal: &str, radix: u32, range: StartEnd) -> Result<ParseToken, String> {
+    use std::num::IntErrorKind;
+
+    let res = match radix {
+        10 => i64::from_str_radix(literal, radix),
+        _ => u64::from_str_radix(literal, radix).map(|n| n as i64),
+    };
+    match res {
+        Ok(n) => Ok(ParseToken::Number((n, range))),
+        Err(e) if *e.kind() == IntErrorKind::PosOverflow => Err("Zahl zu groß".to_string()),
         Err(e) => Err("konnte Zahl nicht parsen".to_string())
     }
+}
+
+/// Der Wert eines Zahlworts wird mit [`f64`] berechnet und muss exakt in
+/// einen [`i64`] passen.
+fn word_number(n: f64) -> Result<i64, String> {
+    if n.fract() != 0f64 || n.abs() >= 9_223_372_036_854_775_808f64 {
+        return Err("Zahl zu groß".to_string())
+    }
+    Ok(n as i64)
 }
\ No newline at end of file
@@ -1,51 +1,50 @@
-use crate::token::WordToken;
-use crate::iter::TripleIter;
+use super::super::token::WordToken;
+use super::super::iter::TripleIter;
+use super::super::types::{Function, StartEnd};
 
 pub struct WordTokenStream {
     data: Vec<WordToken>,
+    /// Stelle jedes Teilworts innerhalb des Wortes in Bytes.
+    spans: Vec<StartEnd>,
 }
 
 impl WordTokenStream {
     pub fn from(input: &str) -> Option<Self> {
         let mut data = Vec::new();
+        let mut spans = Vec::new();
         match WordTokenStream::tokinize_single(input) {
-            Some(token) => data.push(token),
+            Some(token) => {
+                data.push(token);
+                spans.push(StartEnd::from(0, input.len()));
+            }
             None => {
-                let mut part = String::new();
-                for (idx, c) in input.chars().enumerate() {
-                    part.push(c);
-                    match WordTokenStream::tokinize(&part) {
-                        Some(token) => {
-                            match token {
-                                WordToken::Ein => {
-                                    match input.chars().skip(idx+1).next() {
-                                        Some(c) => {
-                                            match c {
-                                                's' => continue,
-                                                _ => {}
-                                            }
-                                        }
-                                        None => return None
-                                    }
-                                }
-                                _ => {}
+                let mut start = 0;
+                for (idx, c) in input.char_indices() {
+                    let end = idx + c.len_utf8();
+                    if let Some(token) = WordTokenStream::tokinize(&input[start..end]) {
+                        if let WordToken::Ein = token {
+                            match input[end..].chars().next() {
+                                Some('s') => continue,
+                                Some(_) => {}
+                                None => return None
                             }
-                            data.push(token);
-                            part.clear();
                         }
-                        None => {}
+                        data.push(token);
+                        spans.push(StartEnd::from(start, end));
+                        start = end;
                     }
                 }
-                if part.len() > 0 { return None }
+                if start != input.len() { return None }
             }
         }
         
         Some(Self {
             data,
+            spans,
         })
     }
 
-    fn tokinize_single(input: &str) -> Option<WordToken> {
+    pub fn tokinize_single(input: &str) -> Option<WordToken> {
         match input {
             "plus" => Some(WordToken::Plus),
             "minus" => Some(WordToken::Minus),
@@ -53,6 +52,40 @@ impl WordTokenStream {
             "durch" => Some(WordToken::Divide),
             "auf" => Some(WordToken::Open),
             "zu" => Some(WordToken::Close),
+            "prozent" => Some(WordToken::Percent),
+            "promille" => Some(WordToken::Permille),
+            "von" => Some(WordToken::Of),
+            "mod" |
+            "modulo" => Some(WordToken::Modulo),
+            "div" => Some(WordToken::IntegerDivide),
+            "fakultät" => Some(WordToken::Factorial),
+            "ggt" => Some(WordToken::Gcd),
+            "kgv" => Some(WordToken::Lcm),
+            "sin" |
+            "sinus" => Some(WordToken::Function(Function::Sin)),
+            "cos" |
+            "kosinus" |
+            "cosinus" => Some(WordToken::Function(Function::Cos)),
+            "tan" |
+            "tangens" => Some(WordToken::Function(Function::Tan)),
+            "exp" => Some(WordToken::Function(Function::Exp)),
+            "ln" => Some(WordToken::Function(Function::Ln)),
+            "wurzel" |
+            "sqrt" => Some(WordToken::Function(Function::Sqrt)),
+            "hoch" => Some(WordToken::Power),
+            "xor" => Some(WordToken::BitXor),
+            "nicht" => Some(WordToken::Not),
+            "in" |
+            "nach" => Some(WordToken::Convert),
+            "heute" => Some(WordToken::Today),
+            "zwischen" => Some(WordToken::Between),
+            "gleich" => Some(WordToken::Equal),
+            "ungleich" => Some(WordToken::NotEqual),
+            "oder" => Some(WordToken::Or),
+            "wenn" => Some(WordToken::If),
+            "dann" => Some(WordToken::Then),
+            "sonst" => Some(WordToken::Else),
+            "null" => Some(WordToken::Number(0)),
             "eins" => Some(WordToken::Number(1)),
             "zwei" => Some(WordToken::Number(2)),
             "drei" => Some(WordToken::Number(3)),
@@ -103,13 +136,17 @@ impl WordTokenStream {
         self.data.len()
     }
 
+    pub fn span(&self, idx: usize) -> StartEnd {
+        self.spans[idx]
+    }
+
     pub fn iter(&self) -> std::slice::Iter<'_, WordToken> {
         self.into_iter()
     }
 
-    pub fn triple_iter(&self) -> TripleIter<WordTokenStream> {
+    pub fn triple_iter(&self) -> TripleIter<'_, WordTokenStream> {
         TripleIter {
-            inner: &self,
+            inner: self,
             len: self.data.len(),
             count: 0usize,
         }
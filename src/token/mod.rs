@@ -1,20 +1,81 @@
-use super::types::{Operator, StartEnd};
+use super::types::{Operator, PrefixOperator, PostfixOperator, Function, StartEnd, Variable};
+use super::decimal::Decimal;
+use super::date::Date;
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-pub enum InputToken {
-    Space,
-    Letter(char),
-    Digit(char),
-    Symbol(char),
-    Whatever(char),
+/// Abschnitt der Eingabe aus dem [`Lexer`](crate::stream::Lexer). Aufeinander
+/// folgende Leerzeichen, Buchstaben und Ziffern bilden jeweils einen Abschnitt,
+/// die Stelle ist in Bytes angegeben.
+#[derive(Debug, Copy, Clone)]
+pub enum InputToken<'a> {
+    Space(StartEnd),
+    Letters((&'a str, StartEnd)),
+    Digits((&'a str, StartEnd)),
+    Symbol((char, StartEnd)),
+    Whatever((char, StartEnd)),
+}
+
+impl InputToken<'_> {
+    /// Stelle des Abschnitts in der Eingabe.
+    pub fn range(&self) -> StartEnd {
+        use InputToken::*;
+
+        match self {
+            Space(range) => *range,
+            Letters((_, range)) |
+            Digits((_, range)) => *range,
+            Symbol((_, range)) |
+            Whatever((_, range)) => *range,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum ParseToken {
     Number((i64, StartEnd)),
+    /// Zahl mit Komma, z.B.: `12,50`
+    Decimal((Decimal, StartEnd)),
+    /// Datum, z.B.: `17.10.2026` oder `heute`
+    Date((Date, StartEnd)),
     Op((Operator, StartEnd)),
+    Prefix((PrefixOperator, StartEnd)),
+    Postfix((PostfixOperator, StartEnd)),
+    Function((Function, StartEnd)),
+    Unit((usize, StartEnd)),
+    Currency((usize, StartEnd)),
+    Variable((Variable, StartEnd)),
     Open(StartEnd),
     Close(StartEnd),
+    Separator(StartEnd),
+    /// `wenn`, `dann` und `sonst` einer Bedingung
+    If(StartEnd),
+    Then(StartEnd),
+    Else(StartEnd),
+}
+
+impl ParseToken {
+    /// Stelle des Tokens in der Eingabe.
+    pub fn range(&self) -> StartEnd {
+        use ParseToken::*;
+
+        match self {
+            Number((_, range)) |
+            Decimal((_, range)) |
+            Date((_, range)) |
+            Op((_, range)) |
+            Prefix((_, range)) |
+            Postfix((_, range)) |
+            Function((_, range)) |
+            Unit((_, range)) |
+            Currency((_, range)) |
+            Variable((_, range)) => *range,
+            Open(range) |
+            Close(range) |
+            Separator(range) |
+            If(range) |
+            Then(range) |
+            Else(range) => *range,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -25,7 +86,29 @@ pub enum WordToken {
     Divide,
     Open,
     Close,
-    Number(u64),
+    Percent,
+    Permille,
+    Of,
+    Modulo,
+    IntegerDivide,
+    Factorial,
+    Gcd,
+    Lcm,
+    /// Funktion mit einem Argument, z.B.: `sinus`
+    Function(Function),
+    Power,
+    BitXor,
+    Not,
+    Convert,
+    Today,
+    Between,
+    Equal,
+    NotEqual,
+    Or,
+    If,
+    Then,
+    Else,
+    Number(i64),
     Ein,
     Eins,
     Elf,
@@ -1,66 +1,150 @@
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Operator {
     Plus,
     Minus,
     Multiply,
     Divide,
+    ImplicitMultiply,
+    Modulo,
+    IntegerDivide,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+    Convert,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+    /// Zusammengesetzte Größe ohne Operator, z.B.: `2 Stunden 30 Minuten`
+    Compound,
+    /// Potenz, z.B.: `2^10` oder `x hoch 2`
+    Power,
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct StartEnd {
-    pub start: usize,
-    pub end: usize,
-}
-
-impl StartEnd {
-    pub fn from(start: usize, end: usize) -> Self {
-        Self {
-            start,
-            end
+impl Operator {
+    /// Bindungsstärke des Operators, höhere Werte binden stärker.
+    /// Die implizite Multiplikation (`2(3+4)`) bindet stärker als
+    /// `*` und `/`, somit ist `1/2(1+1)` gleich `1/(2*(1+1))`.
+    /// Die bitweisen Operatoren binden schwächer als `+` und `-`, danach
+    /// folgen die Umrechnung mit `in`, die Vergleiche und zuletzt `und`
+    /// sowie `oder`. Zusammengesetzte Größen wie `2 h 30 min` binden
+    /// stärker, am stärksten bindet die Potenz. Diese wird vom rechten
+    /// Operanden aus gelesen, `2^3^2` ist `2^(3^2)`.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Operator::Or => 0,
+            Operator::And => 1,
+            Operator::Less |
+            Operator::Greater |
+            Operator::LessEqual |
+            Operator::GreaterEqual |
+            Operator::Equal |
+            Operator::NotEqual => 2,
+            Operator::Convert => 3,
+            Operator::BitOr => 4,
+            Operator::BitXor => 5,
+            Operator::BitAnd => 6,
+            Operator::ShiftLeft |
+            Operator::ShiftRight => 7,
+            Operator::Plus |
+            Operator::Minus => 8,
+            Operator::Multiply |
+            Operator::Divide |
+            Operator::Modulo |
+            Operator::IntegerDivide => 9,
+            Operator::ImplicitMultiply => 10,
+            Operator::Compound => 11,
+            Operator::Power => 12,
         }
     }
+
+    pub fn is_comparison(&self) -> bool {
+        matches!(self, Operator::Less | Operator::Greater | Operator::LessEqual | Operator::GreaterEqual | Operator::Equal | Operator::NotEqual)
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub enum ParseOperatorKind {
-    None,
-    Strong,
-    Week,
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PrefixOperator {
+    Minus,
+    /// Logisches `nicht` für Vergleiche, bitweises `nicht` für Zahlen
+    Not,
 }
 
-pub struct ParseOperator {
-    idx: usize,
-    kind: Option<Operator>,
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PostfixOperator {
+    Percent,
+    Permille,
+    Factorial,
+    /// Index in die Einheitentabelle, z.B.: `3 Meter`
+    Unit(usize),
+    /// Index in die Währungstabelle, z.B.: `12,50 €`
+    Currency(usize),
 }
 
-impl ParseOperator {
-    pub fn new() -> Self {
-        Self {
-            idx: 0,
-            kind: None,
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Function {
+    Gcd,
+    Lcm,
+    /// Differenz zweier Werte, z.B.: `Tage zwischen 1.1.2026 und 24.12.2026`
+    Between,
+    /// Winkelfunktionen im Bogenmaß, z.B.: `sinus von x` oder `sin(x)`
+    Sin,
+    Cos,
+    Tan,
+    Exp,
+    /// Natürlicher Logarithmus
+    Ln,
+    /// Quadratwurzel, z.B.: `wurzel(2)`
+    Sqrt,
+}
+
+impl Function {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Function::Gcd => "ggT",
+            Function::Lcm => "kgV",
+            Function::Between => "zwischen",
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Tan => "tan",
+            Function::Exp => "exp",
+            Function::Ln => "ln",
+            Function::Sqrt => "wurzel",
         }
     }
 
-    pub fn idx(&self) -> usize{
-        self.idx
+    /// Funktion mit genau einem Argument, dieses kann auch ohne Klammern
+    /// folgen, z.B.: `sinus von x`.
+    pub fn is_unary(&self) -> bool {
+        !matches!(self, Function::Gcd | Function::Lcm | Function::Between)
     }
+}
 
-    pub fn kind(&self) -> Operator {
-        match self.kind {
-            Some(op) => op,
-            None => panic!("Kind is None")
-        }
-    }
+/// Variable eines Ausdrucks, z.B.: `x` in `3x + 5`. Der Wert steht bei der
+/// Auswertung an der Stelle `idx` der Belegung.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Variable {
+    pub name: &'static str,
+    pub idx: usize,
+}
 
-    pub fn set(&mut self, idx: usize, kind: Operator) {
-        self.idx = idx;
-        self.kind = Some(kind);
-    }
+#[derive(Debug, Copy, Clone)]
+pub struct StartEnd {
+    pub start: usize,
+    pub end: usize,
+}
 
-    pub fn is_none(&self) -> bool {
-        match self.kind {
-            Some(_) => false,
-            None => true
+impl StartEnd {
+    pub fn from(start: usize, end: usize) -> Self {
+        Self {
+            start,
+            end
         }
     }
-}
\ No newline at end of file
+}
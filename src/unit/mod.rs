@@ -0,0 +1,179 @@
+//! Physikalische Einheiten. Jede Einheit ist über einen Faktor und ihre
+//! Dimension auf die SI-Basiseinheiten zurückgeführt.
+
+/// Exponenten der SI-Basiseinheiten in der Reihenfolge
+/// Meter, Kilogramm, Sekunde, Ampere, Kelvin, Mol und Candela.
+pub type Dimension = [i8; 7];
+
+pub const DIMENSIONLESS: Dimension = [0; 7];
+
+pub struct UnitDef {
+    /// Symbol der Einheit für die Ausgabe.
+    pub symbol: &'static str,
+    /// Namen und Abkürzungen in Kleinbuchstaben.
+    pub names: &'static [&'static str],
+    /// Faktor zur SI-Einheit.
+    pub factor: f64,
+    pub dimension: Dimension,
+}
+
+const LENGTH: Dimension = [1, 0, 0, 0, 0, 0, 0];
+const AREA: Dimension = [2, 0, 0, 0, 0, 0, 0];
+const VOLUME: Dimension = [3, 0, 0, 0, 0, 0, 0];
+const MASS: Dimension = [0, 1, 0, 0, 0, 0, 0];
+pub const TIME: Dimension = [0, 0, 1, 0, 0, 0, 0];
+const CURRENT: Dimension = [0, 0, 0, 1, 0, 0, 0];
+const TEMPERATURE: Dimension = [0, 0, 0, 0, 1, 0, 0];
+const AMOUNT: Dimension = [0, 0, 0, 0, 0, 1, 0];
+const LUMINOSITY: Dimension = [0, 0, 0, 0, 0, 0, 1];
+const FREQUENCY: Dimension = [0, 0, -1, 0, 0, 0, 0];
+const FORCE: Dimension = [1, 1, -2, 0, 0, 0, 0];
+const PRESSURE: Dimension = [-1, 1, -2, 0, 0, 0, 0];
+const ENERGY: Dimension = [2, 1, -2, 0, 0, 0, 0];
+const POWER: Dimension = [2, 1, -3, 0, 0, 0, 0];
+const VOLTAGE: Dimension = [2, 1, -3, -1, 0, 0, 0];
+const RESISTANCE: Dimension = [2, 1, -3, -2, 0, 0, 0];
+
+pub static UNITS: &[UnitDef] = &[
+    UnitDef { symbol: "m", names: &["meter", "m"], factor: 1f64, dimension: LENGTH },
+    UnitDef { symbol: "km", names: &["kilometer", "km"], factor: 1e3, dimension: LENGTH },
+    UnitDef { symbol: "dm", names: &["dezimeter", "dm"], factor: 1e-1, dimension: LENGTH },
+    UnitDef { symbol: "cm", names: &["zentimeter", "cm"], factor: 1e-2, dimension: LENGTH },
+    UnitDef { symbol: "mm", names: &["millimeter", "mm"], factor: 1e-3, dimension: LENGTH },
+    UnitDef { symbol: "sm", names: &["seemeile", "seemeilen", "sm"], factor: 1852f64, dimension: LENGTH },
+    UnitDef { symbol: "m²", names: &["quadratmeter", "qm"], factor: 1f64, dimension: AREA },
+    UnitDef { symbol: "ha", names: &["hektar", "ha"], factor: 1e4, dimension: AREA },
+    UnitDef { symbol: "m³", names: &["kubikmeter"], factor: 1f64, dimension: VOLUME },
+    UnitDef { symbol: "l", names: &["liter", "l"], factor: 1e-3, dimension: VOLUME },
+    UnitDef { symbol: "ml", names: &["milliliter", "ml"], factor: 1e-6, dimension: VOLUME },
+    UnitDef { symbol: "kg", names: &["kilogramm", "kg"], factor: 1f64, dimension: MASS },
+    UnitDef { symbol: "g", names: &["gramm", "g"], factor: 1e-3, dimension: MASS },
+    UnitDef { symbol: "mg", names: &["milligramm", "mg"], factor: 1e-6, dimension: MASS },
+    UnitDef { symbol: "t", names: &["tonne", "tonnen", "t"], factor: 1e3, dimension: MASS },
+    UnitDef { symbol: "s", names: &["sekunde", "sekunden", "s"], factor: 1f64, dimension: TIME },
+    UnitDef { symbol: "ms", names: &["millisekunde", "millisekunden", "ms"], factor: 1e-3, dimension: TIME },
+    UnitDef { symbol: "min", names: &["minute", "minuten", "min"], factor: 60f64, dimension: TIME },
+    UnitDef { symbol: "h", names: &["stunde", "stunden", "h"], factor: 3600f64, dimension: TIME },
+    UnitDef { symbol: "d", names: &["tag", "tage", "tagen"], factor: 86400f64, dimension: TIME },
+    UnitDef { symbol: "Wo", names: &["woche", "wochen"], factor: 604800f64, dimension: TIME },
+    UnitDef { symbol: "A", names: &["ampere", "a"], factor: 1f64, dimension: CURRENT },
+    UnitDef { symbol: "mA", names: &["milliampere", "ma"], factor: 1e-3, dimension: CURRENT },
+    UnitDef { symbol: "K", names: &["kelvin", "k"], factor: 1f64, dimension: TEMPERATURE },
+    UnitDef { symbol: "mol", names: &["mol"], factor: 1f64, dimension: AMOUNT },
+    UnitDef { symbol: "cd", names: &["candela", "cd"], factor: 1f64, dimension: LUMINOSITY },
+    UnitDef { symbol: "Hz", names: &["hertz", "hz"], factor: 1f64, dimension: FREQUENCY },
+    UnitDef { symbol: "N", names: &["newton", "n"], factor: 1f64, dimension: FORCE },
+    UnitDef { symbol: "Pa", names: &["pascal", "pa"], factor: 1f64, dimension: PRESSURE },
+    UnitDef { symbol: "bar", names: &["bar"], factor: 1e5, dimension: PRESSURE },
+    UnitDef { symbol: "J", names: &["joule", "j"], factor: 1f64, dimension: ENERGY },
+    UnitDef { symbol: "kWh", names: &["kilowattstunde", "kilowattstunden", "kwh"], factor: 3.6e6, dimension: ENERGY },
+    UnitDef { symbol: "W", names: &["watt", "w"], factor: 1f64, dimension: POWER },
+    UnitDef { symbol: "kW", names: &["kilowatt", "kw"], factor: 1e3, dimension: POWER },
+    UnitDef { symbol: "V", names: &["volt", "v"], factor: 1f64, dimension: VOLTAGE },
+    UnitDef { symbol: "Ω", names: &["ohm"], factor: 1f64, dimension: RESISTANCE },
+];
+
+/// Sucht eine Einheit anhand ihres Namens oder ihrer Abkürzung.
+pub fn find(name: &str) -> Option<usize> {
+    UNITS.iter().position(|unit| unit.names.contains(&name))
+}
+
+/// Zusammengesetzte Einheit für die Ausgabe, z.B.: `km/h`. Besteht aus den
+/// Symbolen der Einheiten mit ihren Exponenten.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    parts: Vec<(&'static str, i32)>,
+    factor: f64,
+}
+
+impl Unit {
+    pub fn from(idx: usize) -> Self {
+        Self {
+            parts: vec![(UNITS[idx].symbol, 1)],
+            factor: UNITS[idx].factor,
+        }
+    }
+
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    pub fn multiply(&self, other: &Unit) -> Self {
+        self.combine(other, 1)
+    }
+
+    pub fn divide(&self, other: &Unit) -> Self {
+        self.combine(other, -1)
+    }
+
+    fn combine(&self, other: &Unit, sign: i32) -> Self {
+        let mut parts = self.parts.clone();
+        for (symbol, exponent) in &other.parts {
+            match parts.iter_mut().find(|(s, _)| s == symbol) {
+                Some(part) => part.1 += sign * exponent,
+                None => parts.push((symbol, sign * exponent))
+            }
+        }
+        parts.retain(|(_, exponent)| *exponent != 0);
+
+        Self {
+            parts,
+            factor: self.factor * other.factor.powi(sign),
+        }
+    }
+
+    /// Kehrwert der Einheit, z.B.: für `1 / 2 s`.
+    pub fn invert(&self) -> Self {
+        Self {
+            parts: self.parts.iter().map(|(symbol, exponent)| (*symbol, -exponent)).collect(),
+            factor: 1f64 / self.factor,
+        }
+    }
+
+    pub fn symbol(&self) -> String {
+        fn part(symbol: &str, exponent: i32) -> String {
+            match exponent {
+                1 => symbol.to_string(),
+                2 => format!("{}²", symbol),
+                3 => format!("{}³", symbol),
+                _ => format!("{}^{}", symbol, exponent),
+            }
+        }
+
+        let numerator: Vec<String> = self.parts.iter()
+            .filter(|(_, exponent)| *exponent > 0)
+            .map(|(symbol, exponent)| part(symbol, *exponent))
+            .collect();
+        let denominator: Vec<String> = self.parts.iter()
+            .filter(|(_, exponent)| *exponent < 0)
+            .map(|(symbol, exponent)| part(symbol, -exponent))
+            .collect();
+
+        let mut symbol = match numerator.len() {
+            0 => "1".to_string(),
+            _ => numerator.join("·"),
+        };
+        match denominator.len() {
+            0 => {}
+            1 => symbol = format!("{}/{}", symbol, denominator[0]),
+            _ => symbol = format!("{}/({})", symbol, denominator.join("·")),
+        }
+        symbol
+    }
+}
+
+pub fn multiply(left: &Dimension, right: &Dimension) -> Dimension {
+    let mut res = *left;
+    for (idx, exponent) in right.iter().enumerate() {
+        res[idx] += exponent;
+    }
+    res
+}
+
+pub fn divide(left: &Dimension, right: &Dimension) -> Dimension {
+    let mut res = *left;
+    for (idx, exponent) in right.iter().enumerate() {
+        res[idx] -= exponent;
+    }
+    res
+}
@@ -0,0 +1,355 @@
+//! Ergebnis einer Auswertung. Neben einfachen Zahlen gibt es physikalische
+//! Größen mit Einheit, Geldbeträge, Kalenderdaten und Wahrheitswerte.
+
+use std::cmp::Ordering;
+
+use super::currency::{self, RateTable};
+use super::date::Date;
+use super::decimal::Decimal;
+use super::unit::{self, Dimension, Unit, DIMENSIONLESS};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Quantity(Quantity),
+    Money(Money),
+    Date(Date),
+    /// Ergebnis eines Vergleichs, wird als `wahr` oder `falsch` ausgegeben.
+    Bool(bool),
+}
+
+/// Physikalische Größe. Der Wert ist in SI-Einheiten gespeichert, die
+/// Einheit bestimmt nur die Ausgabe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub dimension: Dimension,
+    pub unit: Unit,
+}
+
+/// Geldbetrag, es wird exakt im Dezimalsystem gerechnet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Money {
+    pub amount: Decimal,
+    /// Index in die Währungstabelle.
+    pub currency: usize,
+}
+
+impl Money {
+    pub fn from(n: f64, currency: usize) -> Result<Self, String> {
+        match Decimal::from_f64(n) {
+            Some(amount) => Ok(Self { amount, currency }),
+            None => Err("Betrag zu groß".to_string())
+        }
+    }
+
+    /// Rechnet den Betrag in eine andere Währung um.
+    pub fn convert(&self, currency: usize, rates: Option<&RateTable>) -> Result<Money, String> {
+        if self.currency == currency {
+            return Ok(self.clone())
+        }
+        match rates {
+            Some(rates) => {
+                let amount = rates.convert(&self.amount, self.currency, currency)?;
+                Ok(Money { amount, currency })
+            }
+            None => Err(format!(
+                "{} nicht in {} umrechenbar, Kurse mit \"kurse <datei>\" laden",
+                currency::CURRENCIES[self.currency].code,
+                currency::CURRENCIES[currency].code
+            ))
+        }
+    }
+}
+
+impl Quantity {
+    /// Größe aus einer Zahl in der Einheit `idx` der Einheitentabelle.
+    pub fn from(n: f64, idx: usize) -> Self {
+        let unit = Unit::from(idx);
+        Self {
+            value: n * unit.factor(),
+            dimension: unit::UNITS[idx].dimension,
+            unit,
+        }
+    }
+
+    /// Wert in der Einheit der Ausgabe.
+    pub fn display_value(&self) -> f64 {
+        self.value / self.unit.factor()
+    }
+}
+
+impl Value {
+    /// Zahl bzw. Wert einer Größe falls dieser `unendlich` oder `nicht definiert` ist.
+    pub fn non_finite(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) if !n.is_finite() => Some(*n),
+            Value::Quantity(q) if !q.value.is_finite() => Some(q.value),
+            _ => None
+        }
+    }
+
+    /// Gibt die Zahl zurück, Größen mit Einheit sind nicht erlaubt.
+    pub fn number(&self, name: &str) -> Result<f64, String> {
+        match self {
+            Value::Number(n) => Ok(*n),
+            Value::Quantity(q) => Err(format!("{} nicht mit Einheit {} möglich", name, q.unit.symbol())),
+            Value::Money(m) => Err(format!("{} nicht mit Währung {} möglich", name, currency::CURRENCIES[m.currency].code)),
+            Value::Date(_) => Err(format!("{} nicht mit einem Datum möglich", name)),
+            Value::Bool(_) => Err(format!("{} nicht mit einem Wahrheitswert möglich", name)),
+        }
+    }
+
+    fn dimension(&self) -> Dimension {
+        match self {
+            Value::Number(_) |
+            Value::Money(_) |
+            Value::Date(_) |
+            Value::Bool(_) => DIMENSIONLESS,
+            Value::Quantity(q) => q.dimension,
+        }
+    }
+
+    fn si(&self) -> f64 {
+        match self {
+            Value::Number(n) => *n,
+            Value::Quantity(q) => q.value,
+            Value::Money(m) => m.amount.to_f64(),
+            // Dates and booleans have no numerical value
+            Value::Date(_) |
+            Value::Bool(_) => f64::NAN,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Value::Number(_) => "Zahl".to_string(),
+            Value::Quantity(q) => q.unit.symbol(),
+            Value::Money(m) => currency::CURRENCIES[m.currency].code.to_string(),
+            Value::Date(_) => "Datum".to_string(),
+            Value::Bool(_) => "Wahrheitswert".to_string(),
+        }
+    }
+
+    /// Wendet `f` auf den Wert an, die Einheit bleibt erhalten. Geldbeträge
+    /// und Daten werden dabei nicht verändert, siehe [`Value::neg`] und
+    /// [`Value::mul`].
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Value {
+        match self {
+            Value::Number(n) => Value::Number(f(*n)),
+            Value::Quantity(q) => Value::Quantity(Quantity {
+                value: f(q.value),
+                dimension: q.dimension,
+                unit: q.unit.clone(),
+            }),
+            Value::Money(m) => Value::Money(m.clone()),
+            Value::Date(d) => Value::Date(*d),
+            Value::Bool(b) => Value::Bool(*b),
+        }
+    }
+
+    pub fn neg(&self) -> Result<Value, String> {
+        match self {
+            Value::Money(m) => Ok(Value::Money(Money {
+                amount: m.amount.neg(),
+                currency: m.currency,
+            })),
+            Value::Date(_) => Err("Vorzeichen nicht mit einem Datum möglich".to_string()),
+            Value::Bool(_) => Err("Vorzeichen nicht mit einem Wahrheitswert möglich".to_string()),
+            _ => Ok(self.map(|n| -n)),
+        }
+    }
+
+    /// Anzahl der Tage einer Zeitdauer, z.B.: für `17.10.2026 plus 3 Wochen`.
+    fn whole_days(&self) -> Result<i64, String> {
+        match self {
+            Value::Quantity(q) if q.dimension == unit::TIME => {
+                let days = q.value / 86_400f64;
+                if days.fract() != 0f64 || days.abs() > 1e7 {
+                    return Err("Nur ganze Tage zu einem Datum addierbar".to_string())
+                }
+                Ok(days as i64)
+            }
+            _ => Err(format!("Datum und {} nicht addierbar", self.describe()))
+        }
+    }
+
+    fn shift_date(date: &Date, days: i64) -> Result<Value, String> {
+        match date.add_days(days) {
+            Some(date) => Ok(Value::Date(date)),
+            None => Err("Datum außerhalb der Jahre 1 bis 9999".to_string())
+        }
+    }
+
+    /// Erstellt aus Wert, Dimension und Einheit eine Größe oder eine Zahl
+    /// falls sich die Dimensionen aufheben.
+    fn quantity(value: f64, dimension: Dimension, unit: Unit) -> Value {
+        if dimension == DIMENSIONLESS {
+            Value::Number(value)
+        } else {
+            Value::Quantity(Quantity { value, dimension, unit })
+        }
+    }
+
+    pub fn add(&self, other: &Value) -> Result<Value, String> {
+        match (self, other) {
+            (Value::Money(a), Value::Money(b)) => return Self::money_add_sub(a, b, "addierbar", |a, b| a.add(b)),
+            (Value::Date(d), duration) |
+            (duration, Value::Date(d)) => return Self::shift_date(d, duration.whole_days()?),
+            _ => {}
+        }
+        self.add_sub(other, "addierbar", |a, b| a + b)
+    }
+
+    pub fn sub(&self, other: &Value) -> Result<Value, String> {
+        match (self, other) {
+            (Value::Money(a), Value::Money(b)) => return Self::money_add_sub(a, b, "subtrahierbar", |a, b| a.sub(b)),
+            (Value::Date(a), Value::Date(b)) => {
+                let days = a.days_since(b) as f64;
+                return Ok(Value::Quantity(Quantity::from(days, unit::find("tage").unwrap())))
+            }
+            (Value::Date(d), duration) => return Self::shift_date(d, -duration.whole_days()?),
+            (_, Value::Date(_)) => return Err(format!("Datum nicht von {} subtrahierbar", self.describe())),
+            _ => {}
+        }
+        self.add_sub(other, "subtrahierbar", |a, b| a - b)
+    }
+
+    fn money_add_sub(a: &Money, b: &Money, verb: &str, f: impl Fn(&Decimal, &Decimal) -> Option<Decimal>) -> Result<Value, String> {
+        if a.currency != b.currency {
+            return Err(format!(
+                "{} und {} nicht {}",
+                currency::CURRENCIES[a.currency].code,
+                currency::CURRENCIES[b.currency].code,
+                verb
+            ))
+        }
+        match f(&a.amount, &b.amount) {
+            Some(amount) => Ok(Value::Money(Money { amount, currency: a.currency })),
+            None => Err("Betrag zu groß".to_string())
+        }
+    }
+
+    fn add_sub(&self, other: &Value, verb: &str, f: impl Fn(f64, f64) -> f64) -> Result<Value, String> {
+        if let (Value::Money(_) | Value::Bool(_), _) | (_, Value::Money(_) | Value::Bool(_)) = (self, other) {
+            return Err(format!("{} und {} nicht {}", self.describe(), other.describe(), verb))
+        }
+        if self.dimension() != other.dimension() {
+            return Err(format!("{} und {} nicht {}", self.describe(), other.describe(), verb))
+        }
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => Ok(Value::Number(f(*a, *b))),
+            (Value::Quantity(q), _) => Ok(Value::quantity(f(q.value, other.si()), q.dimension, q.unit.clone())),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn mul(&self, other: &Value) -> Result<Value, String> {
+        let res = match (self, other) {
+            (Value::Number(a), Value::Number(b)) => Value::Number(a * b),
+            (Value::Number(n), Value::Quantity(_)) => other.map(|v| v * n),
+            (Value::Quantity(_), Value::Number(n)) => self.map(|v| v * n),
+            (Value::Quantity(a), Value::Quantity(b)) => {
+                let dimension = unit::multiply(&a.dimension, &b.dimension);
+                Value::quantity(a.value * b.value, dimension, a.unit.multiply(&b.unit))
+            }
+            (Value::Money(m), Value::Number(n)) |
+            (Value::Number(n), Value::Money(m)) => {
+                let factor = Money::from(*n, m.currency)?;
+                match m.amount.mul(&factor.amount) {
+                    Some(amount) => Value::Money(Money { amount, currency: m.currency }),
+                    None => return Err("Betrag zu groß".to_string())
+                }
+            }
+            _ => return Err(format!("{} und {} nicht multiplizierbar", self.describe(), other.describe()))
+        };
+        Ok(res)
+    }
+
+    pub fn div(&self, other: &Value) -> Result<Value, String> {
+        if other.si() == 0f64 {
+            return Err("Teilen durch Null nicht möglich".to_string())
+        }
+        let res = match (self, other) {
+            (Value::Money(m), Value::Number(n)) => {
+                let divisor = Money::from(*n, m.currency)?;
+                match m.amount.div(&divisor.amount) {
+                    Some(amount) => Value::Money(Money { amount, currency: m.currency }),
+                    None => return Err("Betrag zu groß".to_string())
+                }
+            }
+            (Value::Money(a), Value::Money(b)) if a.currency == b.currency => {
+                match a.amount.div(&b.amount) {
+                    Some(ratio) => Value::Number(ratio.to_f64()),
+                    None => return Err("Betrag zu groß".to_string())
+                }
+            }
+            (Value::Money(_) | Value::Date(_) | Value::Bool(_), _) |
+            (_, Value::Money(_) | Value::Date(_) | Value::Bool(_)) => return Err(format!("{} und {} nicht teilbar", self.describe(), other.describe())),
+            (Value::Number(a), Value::Number(b)) => Value::Number(a / b),
+            (Value::Quantity(_), Value::Number(n)) => self.map(|v| v / n),
+            (Value::Number(n), Value::Quantity(q)) => {
+                let dimension = unit::divide(&DIMENSIONLESS, &q.dimension);
+                Value::quantity(n / q.value, dimension, q.unit.invert())
+            }
+            (Value::Quantity(a), Value::Quantity(b)) => {
+                let dimension = unit::divide(&a.dimension, &b.dimension);
+                Value::quantity(a.value / b.value, dimension, a.unit.divide(&b.unit))
+            }
+        };
+        Ok(res)
+    }
+
+    /// Rechnet die Größe in die Einheit von `target` um, z.B.: `3 m in mm`.
+    /// Geldbeträge werden mit der Kurstabelle `rates` umgerechnet.
+    pub fn convert(&self, target: &Value, rates: Option<&RateTable>) -> Result<Value, String> {
+        let unit = match (self, target) {
+            (Value::Money(m), Value::Money(t)) => return m.convert(t.currency, rates).map(Value::Money),
+            (_, Value::Money(_) | Value::Date(_) | Value::Bool(_)) |
+            (Value::Money(_) | Value::Date(_) | Value::Bool(_), _) => return Err(format!("{} nicht in {} umrechenbar", self.describe(), target.describe())),
+            (_, Value::Quantity(q)) => q.unit.clone(),
+            (_, Value::Number(_)) => return Err("Umrechnung nur in eine Einheit möglich".to_string())
+        };
+        if self.dimension() != target.dimension() {
+            return Err(format!("{} nicht in {} umrechenbar", self.describe(), target.describe()))
+        }
+        Ok(Value::Quantity(Quantity {
+            value: self.si(),
+            dimension: self.dimension(),
+            unit,
+        }))
+    }
+
+    /// Gibt den Wahrheitswert zurück, `name` ist der Operator für die
+    /// Fehlermeldung.
+    pub fn boolean(&self, name: &str) -> Result<bool, String> {
+        match self {
+            Value::Bool(b) => Ok(*b),
+            _ => Err(format!("\"{}\" erwartet wahr oder falsch, nicht {}", name, self.describe()))
+        }
+    }
+
+    /// Vergleicht zwei Werte. Zahlen und Größen mit einem relativen
+    /// Unterschied unter 1e-12 gelten als gleich, somit ist `0,1 + 0,2 = 0,3`
+    /// wahr. Wahrheitswerte können nur auf Gleichheit geprüft werden.
+    pub fn compare(&self, other: &Value) -> Result<Ordering, String> {
+        let res = match (self, other) {
+            (Value::Money(a), Value::Money(b)) if a.currency == b.currency => a.amount.partial_cmp(&b.amount),
+            (Value::Date(a), Value::Date(b)) => a.partial_cmp(b),
+            (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+            (Value::Number(_) | Value::Quantity(_), Value::Number(_) | Value::Quantity(_)) if self.dimension() == other.dimension() => {
+                let (a, b) = (self.si(), other.si());
+                if (a - b).abs() <= 1e-12 * a.abs().max(b.abs()) {
+                    Some(Ordering::Equal)
+                } else {
+                    a.partial_cmp(&b)
+                }
+            }
+            _ => None
+        };
+        match res {
+            Some(ordering) => Ok(ordering),
+            None => Err(format!("{} und {} nicht vergleichbar", self.describe(), other.describe()))
+        }
+    }
+}
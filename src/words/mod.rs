@@ -0,0 +1,119 @@
+//! Ausgeschriebene deutsche Zahlwörter, z.B.: `einundzwanzig` für `21`.
+
+const UNITS: [&str; 13] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs",
+    "sieben", "acht", "neun", "zehn", "elf", "zwölf",
+];
+
+const TEENS: [&str; 7] = [
+    "dreizehn", "vierzehn", "fünfzehn", "sechzehn", "siebzehn", "achtzehn", "neunzehn",
+];
+
+const TENS: [&str; 8] = [
+    "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig",
+];
+
+/// Schreibt eine Zahl als Wort aus, z.B.: `-3.5` als `minus drei komma fünf`.
+/// Zahlen ab einer Billion oder in Exponentialschreibweise bleiben Ziffern.
+pub fn number(n: f64) -> String {
+    if n.is_nan() {
+        return "nicht definiert".to_string()
+    }
+    if n.is_infinite() {
+        return match n.is_sign_negative() {
+            true => "minus unendlich".to_string(),
+            false => "unendlich".to_string(),
+        }
+    }
+    // `-0` ist `null`
+    let n = if n == 0f64 { 0f64 } else { n };
+    let literal = format!("{}", n);
+    if n.abs() >= 1e12 || literal.contains('e') {
+        return literal
+    }
+
+    let (sign, literal) = match literal.strip_prefix('-') {
+        Some(rest) => ("minus ", rest),
+        None => ("", literal.as_str())
+    };
+    let (integer, fraction) = match literal.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (literal, "")
+    };
+
+    let mut words = format!("{}{}", sign, integer_words(integer.parse().unwrap()));
+    if !fraction.is_empty() {
+        words.push_str(" komma");
+        for c in fraction.chars() {
+            words.push(' ');
+            words.push_str(UNITS[c.to_digit(10).unwrap() as usize]);
+        }
+    }
+    words
+}
+
+fn integer_words(n: u64) -> String {
+    if n == 0 {
+        return UNITS[0].to_string()
+    }
+
+    let billions = n / 1_000_000_000;
+    let millions = n / 1_000_000 % 1000;
+    let rest = n % 1_000_000;
+
+    let mut parts = Vec::new();
+    match billions {
+        0 => {}
+        1 => parts.push("eine Milliarde".to_string()),
+        n => parts.push(format!("{} Milliarden", below_million(n, true))),
+    }
+    match millions {
+        0 => {}
+        1 => parts.push("eine Million".to_string()),
+        n => parts.push(format!("{} Millionen", below_million(n, true))),
+    }
+    if rest > 0 {
+        parts.push(below_million(rest, true));
+    }
+    parts.join(" ")
+}
+
+fn below_million(n: u64, last: bool) -> String {
+    let thousands = n / 1000;
+    let rest = n % 1000;
+    match thousands {
+        0 => below_thousand(rest, last),
+        _ => format!("{}tausend{}", below_thousand(thousands, false), below_thousand(rest, last)),
+    }
+}
+
+/// Zahl unter tausend, `last` unterscheidet `eins` am Ende von `ein` in `eintausend`.
+fn below_thousand(n: u64, last: bool) -> String {
+    let hundreds = (n / 100) as usize;
+    let rest = (n % 100) as usize;
+
+    let mut words = match hundreds {
+        0 => String::new(),
+        1 => "einhundert".to_string(),
+        h => format!("{}hundert", UNITS[h]),
+    };
+    match rest {
+        0 => {}
+        1 if !last => words.push_str("ein"),
+        1..=12 => words.push_str(UNITS[rest]),
+        13..=19 => words.push_str(TEENS[rest - 13]),
+        _ => {
+            let units = rest % 10;
+            match units {
+                0 => {}
+                1 => words.push_str("einund"),
+                u => {
+                    words.push_str(UNITS[u]);
+                    words.push_str("und");
+                }
+            }
+            words.push_str(TENS[rest / 10 - 2]);
+        }
+    }
+    words
+}